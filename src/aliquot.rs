@@ -1,8 +1,13 @@
 //! Module for working with aliquot and divisor sums.
 //!
 //! This module contains functions for calculating the 
-//! aliquot and divisor sums of numbers, along with functions
-//! for testing for perfect numbers and similar concepts.
+//! aliquot and divisor sums of numbers, and inverting the
+//! divisor sum, along with functions for testing for perfect
+//! numbers and similar concepts.
+
+use super::factor;
+use super::figurate::general_pentagonal_number as gpn;
+use super::prime;
 
 /// Return the aliquot sum of a positive integer `n`, 
 /// that is, the sum of all of `n`'s proper divisors.
@@ -51,6 +56,101 @@ pub fn divisor_sum(n: u64) -> u64 {
     aliquot_sum(n) + n
 }
 
+/// Return the divisor sum of a positive integer `n`, computed from
+/// its prime factorization.
+///
+/// Values less than `factor::MAX_SMALL_NUM` are passed to
+/// `divisor_sum()`, as trial division is cheap for them. Larger
+/// values are factored with a new `factor::Factorizer`, see
+/// `divisor_sum_with()` for more information.
+///
+/// Creating a `Factorizer` sieves the small primes, which dominates
+/// the cost of a single call. If the divisor sums of many large values
+/// are being computed, use `divisor_sum_with()` with one `Factorizer`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if the divisor sum of `n` is larger
+/// than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::divisor_sum_fast;
+/// assert_eq!(divisor_sum_fast(28), 56);
+/// assert_eq!(divisor_sum_fast(29), 30);
+/// ```
+pub fn divisor_sum_fast(n: u64) -> u64 {
+    aliquot_sum_lazy(n, &mut None) + n
+}
+
+/// Return the divisor sum of a positive integer `n`, factoring `n`
+/// with `factorizer`.
+///
+/// Rather than testing every candidate divisor up to `sqrt(n)`, as
+/// `divisor_sum()` does, the divisor sum is found with the product
+/// formula
+///
+/// ```text
+/// σ(n) = Π (p^(e + 1) - 1) / (p - 1)
+/// ```
+///
+/// over the prime powers `p^e` of `n`. As long as `factorizer` is
+/// reused between calls, this is faster than `divisor_sum()` for large
+/// values with small factors. Values with two or more prime factors
+/// larger than `factor::MAX_SMALL_NUM` are split with `factor::rho()`,
+/// which can be slower than trial division.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if the divisor sum of `n` is larger
+/// than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::divisor_sum_with;
+/// use reikna::factor::Factorizer;
+/// let factorizer = Factorizer::new();
+/// assert_eq!(divisor_sum_with(28, &factorizer), 56);
+/// assert_eq!(divisor_sum_with(1 << 40, &factorizer), (1 << 41) - 1);
+/// ```
+pub fn divisor_sum_with(n: u64, factorizer: &factor::Factorizer) -> u64 {
+    assert!(n != 0, "divisor sum is only defined for positive integers!");
+
+    let factors = factorizer.factorize(n);
+    let mut sum: u128 = 1;
+    let mut i = 0;
+    while i < factors.len() {
+        let p = factors[i] as u128;
+        let mut term: u128 = 1;
+        let mut power: u128 = 1;
+        while i < factors.len() && factors[i] as u128 == p {
+            power *= p;
+            term += power;
+            i += 1;
+        }
+        sum *= term;
+    }
+
+    assert!(sum <= u64::MAX as u128, "divisor sum of {} is larger than \
+                                      u64::MAX!", n);
+    sum as u64
+}
+
+// helper function to compute the aliquot sum of `n`, using trial
+// division for small values, and creating `factorizer` the first time
+// a large value is seen
+fn aliquot_sum_lazy(n: u64, factorizer: &mut Option<factor::Factorizer>)
+    -> u64 {
+    if n < factor::MAX_SMALL_NUM {
+        return aliquot_sum(n);
+    }
+
+    let factorizer = factorizer.get_or_insert_with(factor::Factorizer::new);
+    divisor_sum_with(n, factorizer) - n
+}
+
 /// Return `true` if `n` is an abundant number,
 /// that is, a number whose aliquot sum is greater
 /// than itself.
@@ -89,6 +189,44 @@ pub fn perfect_number(n: u64) -> bool {
     aliquot_sum(n) == n
 } 
 
+/// Return `true` if `n` passes a set of cheap necessary conditions
+/// for being an odd perfect number.
+///
+/// No odd perfect number is known, but any odd perfect number `n`
+/// must satisfy all of the following, each of which can be checked
+/// without computing the divisor sum of `n`
+///
+/// * `n` is odd
+/// * `n` is not a perfect square, as the divisor sum of an odd square
+///   is odd, so it cannot be `2n`
+/// * `n = 1 mod 12` or `n = 9 mod 36`, by Touchard's theorem
+/// * `n` is not divisible by `105`
+///
+/// Values that pass are only candidates, and should be checked with
+/// `perfect_number()`. In fact any odd perfect number is known to be
+/// larger than `10^1500`, so no `u64` is an odd perfect number, but
+/// this function does not use that bound.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::odd_perfect_candidate;
+/// assert_eq!(odd_perfect_candidate(13), true);
+/// assert_eq!(odd_perfect_candidate(15), false); // 15 = 3 mod 12
+/// assert_eq!(odd_perfect_candidate(81), false); // 81 = 9^2
+/// ```
+pub fn odd_perfect_candidate(n: u64) -> bool {
+    if n & 0x01 == 0 {
+        return false;
+    }
+
+    if n % 12 != 1 && n % 36 != 9 {
+        return false;
+    }
+
+    n % 105 != 0 && !factor::perfect_square(n)
+}
+
 /// Return `true` if `n` is a deficient number,
 /// that is, a number whose aliquot sum is less
 /// than itself.
@@ -172,6 +310,61 @@ pub fn amicable_number(n: u64) -> bool {
     aliquot_sum(aliquot_sum(n)) == n
 }
 
+/// Return `true` if `n` is an amicable number, computing each
+/// aliquot sum like `divisor_sum_fast()`.
+///
+/// This gives the same result as `amicable_number()`. Values less than
+/// `factor::MAX_SMALL_NUM` use trial division, and the small primes are
+/// only sieved, at most once, if a larger value is reached. If many
+/// large values are being tested, use `amicable_number_with()` with one
+/// `factor::Factorizer`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if any divisor sum is larger than
+/// `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::amicable_number_fast;
+/// assert_eq!(amicable_number_fast(2620), true);
+/// assert_eq!(amicable_number_fast(2621), false);
+/// ```
+pub fn amicable_number_fast(n: u64) -> bool {
+    let mut factorizer = None;
+    let sum = aliquot_sum_lazy(n, &mut factorizer);
+
+    aliquot_sum_lazy(sum, &mut factorizer) == n
+}
+
+/// Return `true` if `n` is an amicable number, computing each
+/// aliquot sum with `divisor_sum_with()` and `factorizer`.
+///
+/// This gives the same result as `amicable_number()`, and is faster
+/// when the aliquot sums are large values with small factors, see
+/// `divisor_sum_with()` for more information.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if any divisor sum is larger than
+/// `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::amicable_number_with;
+/// use reikna::factor::Factorizer;
+/// let factorizer = Factorizer::new();
+/// assert_eq!(amicable_number_with(2620, &factorizer), true);
+/// assert_eq!(amicable_number_with(2621, &factorizer), false);
+/// ```
+pub fn amicable_number_with(n: u64, factorizer: &factor::Factorizer) -> bool {
+    let s = |x: u64| divisor_sum_with(x, factorizer) - x;
+
+    s(s(n)) == n
+}
+
 /// Return `true` if `n` is a sociable number,
 /// that is, a number whose aliquot sums form a
 /// cyclic pattern, e.g.
@@ -180,6 +373,9 @@ pub fn amicable_number(n: u64) -> bool {
 /// 14288 -> 15472 -> 14536 -> 14264 -> 12496 -> 14288
 /// ```
 ///
+/// If the aliquot sums of `n` reach a cycle that does not contain
+/// `n` itself, such as `95 -> 25 -> 6 -> 6`, `false` is returned.
+///
 /// # Panics
 /// 
 /// Panics if `n` is zero.
@@ -192,12 +388,405 @@ pub fn amicable_number(n: u64) -> bool {
 /// assert_eq!(sociable_number(14289), false);
 /// ```
 pub fn sociable_number(n: u64) -> bool {
-    let mut x = aliquot_sum(n);
+    returns_to(n, aliquot_sum)
+}
+
+/// Return `true` if `n` is a sociable number, computing each
+/// aliquot sum like `divisor_sum_fast()`.
+///
+/// This gives the same result as `sociable_number()`. Values less than
+/// `factor::MAX_SMALL_NUM` use trial division, and the small primes are
+/// only sieved, at most once, if a larger value is reached. If many
+/// large values are being tested, use `sociable_number_with()` with one
+/// `factor::Factorizer`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if any divisor sum is larger than
+/// `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::sociable_number_fast;
+/// assert_eq!(sociable_number_fast(14288), true);
+/// assert_eq!(sociable_number_fast(14289), false);
+/// ```
+pub fn sociable_number_fast(n: u64) -> bool {
+    let mut factorizer = None;
+
+    returns_to(n, |x| aliquot_sum_lazy(x, &mut factorizer))
+}
+
+/// Return `true` if `n` is a sociable number, computing each
+/// aliquot sum with `divisor_sum_with()` and `factorizer`.
+///
+/// This gives the same result as `sociable_number()`, and is faster
+/// when the aliquot sums are large values with small factors, see
+/// `divisor_sum_with()` for more information.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if any divisor sum is larger than
+/// `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::sociable_number_with;
+/// use reikna::factor::Factorizer;
+/// let factorizer = Factorizer::new();
+/// assert_eq!(sociable_number_with(14288, &factorizer), true);
+/// assert_eq!(sociable_number_with(14289, &factorizer), false);
+/// ```
+pub fn sociable_number_with(n: u64, factorizer: &factor::Factorizer) -> bool {
+    returns_to(n, |x| divisor_sum_with(x, factorizer) - x)
+}
+
+// helper function to check if repeatedly applying the aliquot sum `s`
+// to `n` leads back to `n`, using Floyd's algorithm to stop once the
+// sequence reaches a cycle that does not contain `n`
+fn returns_to<F: FnMut(u64) -> u64>(n: u64, mut s: F) -> bool {
+    let mut slow = n;
+    let mut fast = n;
     loop {
-        if x == 1 { return false; }
-        if x == n { return true;  }
+        for _ in 0..2 {
+            fast = s(fast);
+            if fast == 1 { return false; }
+            if fast == n { return true;  }
+        }
+
+        slow = s(slow);
+        if slow == fast { return false; }
+    }
+}
+
+/// Return `true` if `n` is a harmonic divisor number, also called
+/// an Ore number, that is, a number whose divisors have a harmonic
+/// mean that is an integer.
+///
+/// The harmonic mean of the divisors of `n` is
+///
+/// ```text
+/// H(n) = n * d(n) / σ(n)
+/// ```
+///
+/// where `d(n)` is the number of divisors of `n` and `σ(n)` is the
+/// divisor sum of `n`. Both are computed from the factorization of `n`
+/// given by `factor::factorize_exp()`, rather than by trial division.
+///
+/// Every perfect number is a harmonic divisor number.
+///
+/// # Panics
+/// 
+/// Panics if `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::is_harmonic_divisor;
+/// assert_eq!(is_harmonic_divisor(140), true);
+/// assert_eq!(is_harmonic_divisor(141), false);
+/// ```
+pub fn is_harmonic_divisor(n: u64) -> bool {
+    assert!(n != 0, "harmonic divisor numbers are only defined for \
+                     positive integers!");
+
+    let mut num_divisors: u128 = 1;
+    let mut sum: u128 = 1;
+    for (p, e) in factor::factorize_exp(n) {
+        num_divisors *= e as u128 + 1;
+        sum *= ((p as u128).pow(e + 1) - 1) / (p as u128 - 1);
+    }
+
+    (n as u128 * num_divisors) % sum == 0
+}
+
+/// Return a `Vec<u64>` of the even perfect numbers in [1, `max`].
+///
+/// Rather than testing each number with `perfect_number()`, this
+/// function generates the perfect numbers directly using the
+/// Euclid-Euler theorem: for every Mersenne prime `2^p - 1`,
+///
+/// ```text
+/// 2^(p - 1) * (2^p - 1)
+/// ```
+///
+/// is a perfect number, and every even perfect number is of
+/// this form. Mersenne primes are found with `prime::lucas_lehmer()`.
+///
+/// No odd perfect numbers are known, so the result should contain
+/// every perfect number in the range.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::perfect_numbers;
+/// assert_eq!(perfect_numbers(10_000), vec![6, 28, 496, 8128]);
+/// ```
+pub fn perfect_numbers(max: u64) -> Vec<u64> {
+    let mut perfect: Vec<u64> = Vec::new();
+
+    for p in 2..64 {
+        let n = match (1u64 << (p - 1)).checked_mul((1u64 << p) - 1) {
+            Some(n) => n,
+            None    => break,
+        };
+
+        if n > max {
+            break;
+        }
+
+        if prime::lucas_lehmer(p) {
+            perfect.push(n);
+        }
+    }
+
+    perfect
+}
+
+/// Return a `Vec<u64>` of the aliquot sum of every value in [0, `max`].
+///
+/// Rather than calling `aliquot_sum()` for each value, this function
+/// uses a divisor sieve, adding each `d` to the sums of its proper
+/// multiples, which takes `O(n log n)` time in total. This is much
+/// faster for large ranges, at the cost of memory linear in `max`.
+///
+/// The element at index `n` is the aliquot sum of `n`, and the element
+/// at index `0` is zero.
+///
+/// # Panics
+///
+/// Panics if `max` cannot be cast into a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::aliquot_sum_sieve;
+/// assert_eq!(aliquot_sum_sieve(6), vec![0, 0, 1, 1, 3, 1, 6]);
+/// ```
+pub fn aliquot_sum_sieve(max: u64) -> Vec<u64> {
+    assert!(max < usize::MAX as u64,
+            "sieve max {} is larger than machine word size!", max);
+    let max = max as usize;
+
+    let mut sums = vec![0u64; max + 1];
+    for d in 1..(max / 2 + 1) {
+        let mut k = d * 2;
+        while k <= max {
+            sums[k] += d as u64;
+            k += d;
+        }
+    }
+
+    sums
+}
+
+/// Return a `Vec` of the amicable pairs `(a, b)` with
+/// `a < b <= max`.
+///
+/// Rather than calling `amicable_number()` for each value, this
+/// function computes the aliquot sum of every value in [1, `max`]
+/// with `aliquot_sum_sieve()`, then searches the sums for pairs.
+/// This is much faster for large ranges, at the cost of memory
+/// linear in `max`.
+///
+/// The pairs are sorted by their smaller member.
+///
+/// # Panics
+///
+/// Panics if `max` cannot be cast into a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::amicable_pairs;
+/// assert_eq!(amicable_pairs(2000), vec![(220, 284), (1184, 1210)]);
+/// ```
+pub fn amicable_pairs(max: u64) -> Vec<(u64, u64)> {
+    let sums = aliquot_sum_sieve(max);
 
-        x = aliquot_sum(x);
+    let mut pairs: Vec<(u64, u64)> = Vec::new();
+    for a in 1..sums.len() {
+        let b = sums[a];
+        if b as usize > a && b <= max && sums[b as usize] == a as u64 {
+            pairs.push((a as u64, b));
+        }
+    }
+
+    pairs
+}
+
+/// Return a `Vec<u64>` of the divisor sum of every value in [0, `max`],
+/// computed with Euler's pentagonal number recurrence.
+///
+/// The same generalized pentagonal numbers used by the partition 
+/// function give the recurrence
+///
+/// ```text
+/// s(n) = s(n - 1) + s(n - 2) - s(n - 5) - s(n - 7) + s(n - 12) + ...
+/// ```
+///
+/// where a term `s(0)` is replaced by `n` itself, which only happens
+/// when `n` is a generalized pentagonal number. No factoring is needed,
+/// but each value depends on about `sqrt(n)` earlier values, so the
+/// divisor sieve used by `aliquot_sum_sieve()` is faster in practice.
+///
+/// The element at index `n` is the divisor sum of `n`, and the element
+/// at index `0` is zero.
+///
+/// # Panics
+///
+/// Panics if `max` cannot be cast into a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::divisor_sum_recurrence;
+/// assert_eq!(divisor_sum_recurrence(6), vec![0, 1, 3, 4, 7, 6, 12]);
+/// ```
+pub fn divisor_sum_recurrence(max: u64) -> Vec<u64> {
+    assert!(max < usize::MAX as u64,
+            "max {} is larger than machine word size!", max);
+    let max = max as i64;
+
+    let mut sums: Vec<u64> = Vec::with_capacity(max as usize + 1);
+    sums.push(0);
+    for n in 1..(max + 1) {
+        let mut sum: i128 = 0;
+        let mut pent_n: i64 = 1;
+        loop {
+            let pent = gpn(pent_n);
+            if pent > n {
+                break;
+            }
+
+            let term = if pent == n {
+                n as i128
+            } else {
+                sums[(n - pent) as usize] as i128
+            };
+
+            if (pent_n - 1) & 0x03 < 2 {
+                sum += term;
+            } else {
+                sum -= term;
+            }
+
+            pent_n += 1;
+        }
+
+        sums.push(sum as u64);
+    }
+
+    sums
+}
+
+/// Return a sorted `Vec<u64>` of every `n` such that the divisor sum
+/// of `n` is `m`.
+///
+/// The divisor sum is multiplicative, so the divisor sum of a solution
+/// is a product of the divisor sums `1 + p + ... + p^k` of the prime 
+/// powers in its factorization. This function finds every prime power
+/// whose divisor sum divides `m`, then searches for the products of
+/// these with distinct primes whose divisor sums multiply to `m`, in
+/// the same way as `totient::inverse_totient()`.
+///
+/// If there are no solutions, such as for `m = 2`, an empty `Vec` is
+/// returned. Every solution is less than `m`, except for `n = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::aliquot::inverse_divisor_sum;
+/// assert_eq!(inverse_divisor_sum(12), vec![6, 11]);
+/// assert_eq!(inverse_divisor_sum(2), vec![]);
+/// ```
+pub fn inverse_divisor_sum(m: u64) -> Vec<u64> {
+    let mut solutions: Vec<u64> = Vec::new();
+    if m == 0 {
+        return solutions;
+    }
+
+    let terms = inverse_divisor_sum_terms(m);
+    inverse_divisor_sum_search(m, &terms, terms.len(), 1, &mut solutions);
+
+    solutions.sort();
+    solutions
+}
+
+// helper function to find the prime powers `p^k` whose divisor sums
+// divide `m`, as `(p^k, divisor sum)` pairs grouped by prime
+fn inverse_divisor_sum_terms(m: u64) -> Vec<Vec<(u64, u64)>> {
+    // divisor sum of p^k, or None if it is larger than u64::MAX
+    let prime_power_sum = |p: u64, k: u32| -> Option<(u64, u64)> {
+        let (mut pk, mut sum) = (1u64, 1u64);
+        for _ in 0..k {
+            pk = pk.checked_mul(p)?;
+            sum = sum.checked_add(pk)?;
+        }
+        Some((pk, sum))
+    };
+
+    let mut found: Vec<(u64, u64, u64)> = Vec::new();
+    for d in factor::divisors(m).into_iter().skip(1) {
+        if prime::is_prime_bpsw(d - 1) {
+            found.push((d - 1, d - 1, d));
+        }
+
+        // for k >= 2, p^k < 1 + p + ... + p^k < (p + 1)^k
+        let mut k = 2;
+        while k < 64 && (1u128 << (k + 1)) - 1 <= d as u128 {
+            let root = (d as f64).powf(1.0 / k as f64) as u64;
+            for p in root.saturating_sub(1)..(root + 2) {
+                if !prime::is_prime_bpsw(p) {
+                    continue;
+                }
+                if let Some((pk, sum)) = prime_power_sum(p, k) {
+                    if sum == d {
+                        found.push((p, pk, sum));
+                    }
+                }
+            }
+            k += 1;
+        }
+    }
+
+    found.sort();
+    found.dedup();
+
+    let mut terms: Vec<Vec<(u64, u64)>> = Vec::new();
+    let mut last = 0;
+    for (p, pk, sum) in found {
+        if p != last {
+            terms.push(Vec::new());
+            last = p;
+        }
+        terms.last_mut().unwrap().push((pk, sum));
+    }
+
+    terms
+}
+
+// helper function to find the solutions of s(n) = m using the prime
+// powers of the first `count` primes of `terms`, `n` is the product
+// so far
+fn inverse_divisor_sum_search(m: u64, terms: &[Vec<(u64, u64)>], 
+                              count: usize, n: u64, solutions: &mut Vec<u64>) {
+    if m == 1 {
+        solutions.push(n);
+    }
+
+    for i in (0..count).rev() {
+        for &(pk, sum) in &terms[i] {
+            if m % sum != 0 {
+                continue;
+            }
+
+            if let Some(val) = n.checked_mul(pk) {
+                inverse_divisor_sum_search(m / sum, terms, i, val, solutions);
+            }
+        }
     }
 }
 
@@ -281,6 +870,153 @@ mod tests {
         assert!(!quasiperfect_number(891770));
     }
 
+#[test]
+    fn t_odd_perfect_candidate() {
+        assert!(odd_perfect_candidate(13));
+        assert!(odd_perfect_candidate(37));
+        assert!(odd_perfect_candidate(45));
+        assert!(odd_perfect_candidate(117));
+
+        assert!(!odd_perfect_candidate(0));
+        assert!(!odd_perfect_candidate(1));
+        assert!(!odd_perfect_candidate(3));
+        assert!(!odd_perfect_candidate(5));
+        assert!(!odd_perfect_candidate(9));
+        assert!(!odd_perfect_candidate(15));
+        assert!(!odd_perfect_candidate(25));
+        assert!(!odd_perfect_candidate(49));
+        assert!(!odd_perfect_candidate(105 * 105 * 13));
+        assert!(!odd_perfect_candidate(u64::MAX));
+
+        for p in perfect_numbers(u64::MAX) {
+            assert!(!odd_perfect_candidate(p));
+        }
+
+        let candidates: Vec<u64> = (0..200).filter(|n| {
+            odd_perfect_candidate(*n)
+        }).collect();
+        assert_eq!(candidates, vec![13, 37, 45, 61, 73, 85, 97, 109, 117, 133,
+                                    145, 153, 157, 181, 189, 193]);
+
+        for n in 0..10_000 {
+            if odd_perfect_candidate(n) {
+                assert!(n % 12 == 1 || n % 36 == 9);
+                assert!(n % 2 == 1 && n % 105 != 0);
+                assert!(!factor::perfect_square(n));
+            }
+        }
+    }
+
+#[test]
+    fn t_is_harmonic_divisor() {
+        for n in [1, 6, 28, 140, 270, 496, 672, 1638, 2970, 6200, 8128] {
+            assert!(is_harmonic_divisor(n));
+        }
+
+        for n in [2, 12, 141, 271, 8129] {
+            assert!(!is_harmonic_divisor(n));
+        }
+
+        for n in perfect_numbers(u64::MAX) {
+            assert!(is_harmonic_divisor(n));
+        }
+
+        let harmonic: Vec<u64> = (1..1_000).filter(|n| is_harmonic_divisor(*n))
+                                           .collect();
+        assert_eq!(harmonic, vec![1, 6, 28, 140, 270, 496, 672]);
+    }
+
+#[test]
+#[should_panic]
+    fn t_is_harmonic_divisor_panic() {
+        is_harmonic_divisor(0);
+    }
+
+#[test]
+    fn t_perfect_numbers() {
+        assert_eq!(perfect_numbers(0), vec![]);
+        assert_eq!(perfect_numbers(6), vec![6]);
+        assert_eq!(perfect_numbers(100_000_000),
+                   vec![6, 28, 496, 8128, 33550336]);
+
+        for n in perfect_numbers(100_000_000) {
+            assert!(perfect_number(n));
+        }
+
+        let scanned: Vec<u64> = (1..10_000).filter(|n| perfect_number(*n))
+                                           .collect();
+        assert_eq!(perfect_numbers(10_000), scanned);
+
+        assert_eq!(*perfect_numbers(u64::MAX).last().unwrap(),
+                   2_305_843_008_139_952_128);
+    }
+
+#[test]
+    fn t_aliquot_sum_sieve() {
+        assert_eq!(aliquot_sum_sieve(0), vec![0]);
+        assert_eq!(aliquot_sum_sieve(1), vec![0, 0]);
+        assert_eq!(aliquot_sum_sieve(6), vec![0, 0, 1, 1, 3, 1, 6]);
+
+        let sums = aliquot_sum_sieve(10_000);
+        assert_eq!(sums.len(), 10_001);
+        for (n, sum) in sums.iter().enumerate().skip(1) {
+            assert_eq!(*sum, aliquot_sum(n as u64));
+        }
+    }
+
+#[test]
+    fn t_divisor_sum_recurrence() {
+        assert_eq!(divisor_sum_recurrence(0), vec![0]);
+        assert_eq!(divisor_sum_recurrence(1), vec![0, 1]);
+        assert_eq!(divisor_sum_recurrence(6), vec![0, 1, 3, 4, 7, 6, 12]);
+
+        let sums = divisor_sum_recurrence(1000);
+        assert_eq!(sums.len(), 1001);
+        for (n, sum) in sums.iter().enumerate().skip(1) {
+            assert_eq!(*sum, divisor_sum(n as u64));
+        }
+
+        let sieve = aliquot_sum_sieve(20_000);
+        let sums = divisor_sum_recurrence(20_000);
+        for n in 1..20_001 {
+            assert_eq!(sums[n], sieve[n] + n as u64);
+        }
+    }
+
+#[test]
+    fn t_inverse_divisor_sum() {
+        assert_eq!(inverse_divisor_sum(0), vec![]);
+        assert_eq!(inverse_divisor_sum(1), vec![1]);
+        assert_eq!(inverse_divisor_sum(2), vec![]);
+        assert_eq!(inverse_divisor_sum(3), vec![2]);
+        assert_eq!(inverse_divisor_sum(12), vec![6, 11]);
+        assert_eq!(inverse_divisor_sum(24), vec![14, 15, 23]);
+        assert_eq!(inverse_divisor_sum(7), vec![4]);
+        assert_eq!(inverse_divisor_sum(31), vec![16, 25]);
+
+        // 2^61 - 1 is prime, so it is a solution for its divisor sum
+        let p = (1 << 61) - 1;
+        assert!(inverse_divisor_sum(p + 1).contains(&p));
+        assert!(inverse_divisor_sum(u64::MAX).contains(&(1 << 63)));
+
+        // every solution is less than m, so the search can be checked
+        // against the divisor sums of every value up to m
+        let max = 2_000;
+        let sums = aliquot_sum_sieve(max);
+        for m in 2..(max + 1) {
+            let brute: Vec<u64> = (1..m).filter(|n| {
+                sums[*n as usize] + n == m
+            }).collect();
+            assert_eq!(inverse_divisor_sum(m), brute);
+        }
+
+        for n in [1_000_003, 123_456_789, 4_294_967_296, 8_589_869_056] {
+            let solutions = inverse_divisor_sum(divisor_sum(n));
+            assert!(solutions.contains(&n));
+            assert!(solutions.iter().all(|s| divisor_sum(*s) == divisor_sum(n)));
+        }
+    }
+
 #[test]
     fn t_sociable() {
         assert!(amicable_number(220));
@@ -289,10 +1025,89 @@ mod tests {
         assert!(amicable_number(10744));
         assert!(!amicable_number(10745));
 
+        assert_eq!(amicable_pairs(0), vec![]);
+        assert_eq!(amicable_pairs(283), vec![]);
+        assert_eq!(amicable_pairs(10_000), vec![(220, 284), (1184, 1210),
+                                                (2620, 2924), (5020, 5564),
+                                                (6232, 6368)]);
+        for (a, b) in amicable_pairs(100_000) {
+            assert!(amicable_number(a));
+            assert!(amicable_number(b));
+        }
+
         assert!(sociable_number(8128));
         assert!(sociable_number(220));
         assert!(sociable_number(1264460));
         assert!(sociable_number(14316));
         assert!(!sociable_number(14313));
+        assert!(!sociable_number(95));
+        assert!(!sociable_number(25));
+        assert!(!sociable_number(562));
+    }
+
+#[test]
+    fn t_divisor_sum_fast() {
+        assert_eq!(divisor_sum_fast(1), 1);
+        assert_eq!(divisor_sum_fast(2), 3);
+        assert_eq!(divisor_sum_fast(28), 56);
+        assert_eq!(divisor_sum_fast(29), 30);
+        assert_eq!(divisor_sum_fast(1 << 62), (1 << 63) - 1);
+        assert_eq!(divisor_sum_fast(1_000_000_007), 1_000_000_008);
+        assert_eq!(divisor_sum_fast(999_983 * 1_000_003),
+                   999_984 * 1_000_004);
+
+        let factorizer = factor::Factorizer::new();
+        let sums = aliquot_sum_sieve(10_000);
+        for (n, sum) in sums.iter().enumerate().skip(1) {
+            assert_eq!(divisor_sum_with(n as u64, &factorizer),
+                       sum + n as u64);
+            assert_eq!(divisor_sum_fast(n as u64), sum + n as u64);
+        }
+
+        for n in (factor::MAX_SMALL_NUM - 10)..(factor::MAX_SMALL_NUM + 10) {
+            assert_eq!(divisor_sum_fast(n), divisor_sum(n));
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_divisor_sum_fast_panic() {
+        divisor_sum_fast(0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_divisor_sum_fast_panic_2() {
+        divisor_sum_fast(3 << 62);
+    }
+
+#[test]
+    fn t_sociable_fast() {
+        let factorizer = factor::Factorizer::new();
+
+        for n in [6, 28, 220, 284, 1184, 1210, 2620, 2924, 5020, 5564, 6232,
+                  6368, 10744, 10745, 2621, 122_265, 139_815] {
+            assert_eq!(amicable_number_fast(n), amicable_number(n));
+            assert_eq!(amicable_number_with(n, &factorizer),
+                       amicable_number(n));
+        }
+
+        for n in [6, 25, 95, 220, 562, 8128, 12496, 14288, 14264, 14289,
+                  14313, 14316, 1264460, 1547860] {
+            assert_eq!(sociable_number_fast(n), sociable_number(n));
+            assert_eq!(sociable_number_with(n, &factorizer),
+                       sociable_number(n));
+        }
+
+        for (a, b) in amicable_pairs(100_000) {
+            assert!(amicable_number_with(a, &factorizer));
+            assert!(amicable_number_with(b, &factorizer));
+        }
+        for n in 2..20_000 {
+            assert_eq!(amicable_number_fast(n), amicable_number(n));
+        }
+        for n in 2..100 {
+            assert_eq!(sociable_number_fast(n), sociable_number(n));
+        }
     }
 }
@@ -0,0 +1,164 @@
+//! Module for working with the Collatz conjecture.
+//!
+//! This module has an iterator over the trajectory of a value
+//! under the Collatz function, also called its hailstone sequence,
+//! and a function for counting the steps it takes to reach one.
+
+/// Return the value after `n` under the Collatz function, that is,
+/// `n / 2` if `n` is even, and `3n + 1` if `n` is odd.
+///
+/// # Panics
+///
+/// Panics if `3n + 1` is larger than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::collatz::collatz_next;
+/// assert_eq!(collatz_next(6), 3);
+/// assert_eq!(collatz_next(3), 10);
+/// ```
+pub fn collatz_next(n: u64) -> u64 {
+    if n & 0x01 == 0 {
+        return n / 2;
+    }
+
+    n.checked_mul(3)
+     .and_then(|val| val.checked_add(1))
+     .expect("Collatz trajectory is larger than u64::MAX!")
+}
+
+/// Return the number of steps it takes for `n` to reach one under
+/// the Collatz function.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, as zero never reaches one.
+///
+/// Panics if any value in the trajectory of `n` is larger than
+/// `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::collatz::collatz_steps;
+/// assert_eq!(collatz_steps(6), 8);
+/// assert_eq!(collatz_steps(27), 111);
+/// ```
+pub fn collatz_steps(n: u64) -> u64 {
+    Collatz::new(n).count() as u64 - 1
+}
+
+/// Iterator over the trajectory of a value under the Collatz function,
+/// starting with the value itself and ending with one.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::collatz::Collatz;
+/// let trajectory: Vec<u64> = Collatz::new(6).collect();
+/// assert_eq!(trajectory, vec![6, 3, 10, 5, 16, 8, 4, 2, 1]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Collatz {
+    next: Option<u64>,
+}
+
+impl Collatz {
+    /// Return a `Collatz` iterator over the trajectory of `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, as zero never reaches one.
+    pub fn new(n: u64) -> Collatz {
+        assert!(n != 0, "the Collatz trajectory of zero never reaches one!");
+        Collatz { next: Some(n) }
+    }
+}
+
+impl Iterator for Collatz {
+    type Item = u64;
+
+    /// Return the next value of the trajectory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the next value is larger than `u64::MAX`.
+    fn next(&mut self) -> Option<u64> {
+        let current = self.next?;
+        self.next = if current == 1 {
+            None
+        } else {
+            Some(collatz_next(current))
+        };
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_collatz_next() {
+        assert_eq!(collatz_next(0), 0);
+        assert_eq!(collatz_next(1), 4);
+        assert_eq!(collatz_next(6), 3);
+        assert_eq!(collatz_next(3), 10);
+        assert_eq!(collatz_next(u64::MAX - 1), u64::MAX / 2);
+        assert_eq!(collatz_next(u64::MAX / 3 - 2), u64::MAX - 5);
+    }
+
+#[test]
+#[should_panic]
+    fn t_collatz_next_panic() {
+        collatz_next(u64::MAX / 3);
+    }
+
+#[test]
+    fn t_collatz_steps() {
+        assert_eq!(collatz_steps(1), 0);
+        assert_eq!(collatz_steps(2), 1);
+        assert_eq!(collatz_steps(6), 8);
+        assert_eq!(collatz_steps(27), 111);
+        assert_eq!(collatz_steps(97), 118);
+        assert_eq!(collatz_steps(837_799), 524);
+        assert_eq!(collatz_steps(1 << 63), 63);
+
+        let longest = (1..10_000).max_by_key(|n| collatz_steps(*n)).unwrap();
+        assert_eq!(longest, 6_171);
+        assert_eq!(collatz_steps(longest), 261);
+    }
+
+#[test]
+#[should_panic]
+    fn t_collatz_steps_panic() {
+        collatz_steps(0);
+    }
+
+#[test]
+    fn t_collatz() {
+        let trajectory: Vec<u64> = Collatz::new(1).collect();
+        assert_eq!(trajectory, vec![1]);
+
+        let trajectory: Vec<u64> = Collatz::new(6).collect();
+        assert_eq!(trajectory, vec![6, 3, 10, 5, 16, 8, 4, 2, 1]);
+
+        let trajectory: Vec<u64> = Collatz::new(27).collect();
+        assert_eq!(trajectory.len(), 112);
+        assert_eq!(trajectory.iter().max(), Some(&9_232));
+
+        for n in 1..2_000 {
+            assert_eq!(Collatz::new(n).next(), Some(n));
+            assert_eq!(Collatz::new(n).last(), Some(1));
+            assert_eq!(Collatz::new(n).count() as u64, collatz_steps(n) + 1);
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_collatz_panic() {
+        Collatz::new(0);
+    }
+}
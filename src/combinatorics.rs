@@ -0,0 +1,361 @@
+//! Module for working with factorials and binomial coefficients.
+//!
+//! This module has functions for computing factorials, falling
+//! and rising factorials, and binomial coefficients without
+//! silently overflowing, as well as related functions such as
+//! the Smarandache function.
+
+use super::factor;
+use super::rational::reduce;
+
+/// Return `n!`, or `None` if the result would overflow a `u64`.
+///
+/// `20!` is the largest factorial that fits in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::factorial;
+/// assert_eq!(factorial(5), Some(120));
+/// assert_eq!(factorial(21), None);
+/// ```
+pub fn factorial(n: u64) -> Option<u64> {
+    falling_factorial(n, n)
+}
+
+/// Return the falling factorial of `n` with `k` terms, that is,
+///
+/// ```text
+/// n * (n - 1) * (n - 2) * ... * (n - k + 1)
+/// ```
+///
+/// or `None` if the result would overflow a `u64`.
+///
+/// If `k` is zero, `1` is returned. If `k` is greater than `n`,
+/// one of the terms is zero, so `0` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::falling_factorial;
+/// assert_eq!(falling_factorial(7, 3), Some(210));
+/// assert_eq!(falling_factorial(3, 7), Some(0));
+/// ```
+pub fn falling_factorial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    let mut product: u64 = 1;
+    for i in 0..k {
+        product = product.checked_mul(n - i)?;
+    }
+
+    Some(product)
+}
+
+/// Return the rising factorial of `n` with `k` terms, that is,
+///
+/// ```text
+/// n * (n + 1) * (n + 2) * ... * (n + k - 1)
+/// ```
+///
+/// or `None` if the result would overflow a `u64`.
+///
+/// If `k` is zero, `1` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::rising_factorial;
+/// assert_eq!(rising_factorial(7, 3), Some(504));
+/// assert_eq!(rising_factorial(0, 3), Some(0));
+/// ```
+pub fn rising_factorial(n: u64, k: u64) -> Option<u64> {
+    if k == 0 {
+        return Some(1);
+    }
+
+    if n == 0 {
+        return Some(0);
+    }
+
+    let mut product: u64 = 1;
+    for i in 0..k {
+        product = product.checked_mul(n.checked_add(i)?)?;
+    }
+
+    Some(product)
+}
+
+/// Return the binomial coefficient `C(n, k)`, or `None` if the
+/// result would overflow a `u64`.
+///
+/// The coefficient is computed incrementally using
+///
+/// ```text
+/// C(m, i) = C(m - 1, i - 1) * m / i
+/// ```
+///
+/// so no intermediate factorials are formed, and `None` is only
+/// returned if the result itself does not fit in a `u64`.
+///
+/// If `k` is greater than `n`, `0` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::binomial;
+/// assert_eq!(binomial(10, 3), Some(120));
+/// assert_eq!(binomial(3, 10), Some(0));
+/// ```
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = if k > n - k { n - k } else { k };
+
+    let mut coef: u128 = 1;
+    for i in 1..(k + 1) {
+        coef = coef * (n - k + i) as u128 / i as u128;
+        if coef > u64::MAX as u128 {
+            return None;
+        }
+    }
+
+    Some(coef as u64)
+}
+
+/// Return the `n`th Bernoulli number as a reduced fraction,
+/// formatted as `(numerator, denominator)`.
+///
+/// The numbers are computed with the recurrence
+///
+/// ```text
+///                 1     m - 1
+/// B(m) = - ---------      Σ    C(m + 1, k) * B(k)
+///            m + 1      k = 0
+/// ```
+///
+/// using exact rational arithmetic, which gives `B(1) = -1/2`.
+/// All Bernoulli numbers with odd index greater than one are zero,
+/// and are returned as `(0, 1)` without any computation.
+///
+/// The denominator is always positive.
+///
+/// # Panics
+///
+/// Panics if `n` is even and greater than `34`, as the numerator of
+/// `B(n)` no longer fits into an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::bernoulli;
+/// assert_eq!(bernoulli(1), (-1, 2));
+/// assert_eq!(bernoulli(4), (-1, 30));
+/// assert_eq!(bernoulli(5), (0, 1));
+/// ```
+pub fn bernoulli(n: u64) -> (i64, i64) {
+    if n == 1 {
+        return (-1, 2);
+    }
+
+    if n & 0x01 == 1 {
+        return (0, 1);
+    }
+
+    assert!(n <= 34, "numerator of B({}) is larger than i64::MAX!", n);
+
+    let mut numbers: Vec<(i128, i128)> = vec![(1, 1)];
+    for m in 1..(n + 1) {
+        if m > 1 && m & 0x01 == 1 {
+            numbers.push((0, 1));
+            continue;
+        }
+
+        let mut sum: (i128, i128) = (0, 1);
+        for k in 0..m {
+            let (num, den) = numbers[k as usize];
+            let c = binomial(m + 1, k).unwrap() as i128;
+            sum = reduce(sum.0 * den + c * num * sum.1, sum.1 * den);
+        }
+
+        numbers.push(reduce(-sum.0, sum.1 * (m + 1) as i128));
+    }
+
+    let (num, den) = numbers[n as usize];
+    (num as i64, den as i64)
+}
+
+/// Return the value of the Smarandache (or Kempner) function for `n`,
+/// that is, the smallest positive `m` such that `n` divides `m!`.
+///
+/// This is computed by finding, for each prime power `p^e` in the
+/// factorization of `n`, the smallest multiple of `p` whose factorial
+/// contains at least `e` factors of `p`, and taking the largest of
+/// these values.
+///
+/// Following OEIS A002034, `smarandache(1)` is `1`, since `m` is
+/// required to be positive.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if `factorize_exp()` panics, see the
+/// documentation of `factorize_exp()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::combinatorics::smarandache;
+/// assert_eq!(smarandache(1), 1);
+/// assert_eq!(smarandache(8), 4);
+/// assert_eq!(smarandache(97), 97);
+/// ```
+pub fn smarandache(n: u64) -> u64 {
+    assert!(n > 0, "smarandache(0) is undefined!");
+
+    let mut max = 1;
+    for (p, e) in factor::factorize_exp(n) {
+        let mut m = 0;
+        let mut count = 0;
+        while count < e {
+            m += p;
+
+            let mut k = m;
+            while k % p == 0 {
+                k /= p;
+                count += 1;
+            }
+        }
+
+        if m > max {
+            max = m;
+        }
+    }
+
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_factorial() {
+        assert_eq!(factorial(0), Some(1));
+        assert_eq!(factorial(1), Some(1));
+        assert_eq!(factorial(2), Some(2));
+        assert_eq!(factorial(5), Some(120));
+        assert_eq!(factorial(10), Some(3_628_800));
+        assert_eq!(factorial(20), Some(2_432_902_008_176_640_000));
+        assert_eq!(factorial(21), None);
+        assert_eq!(factorial(1_000), None);
+    }
+
+#[test]
+    fn t_falling_rising_factorial() {
+        assert_eq!(falling_factorial(0, 0), Some(1));
+        assert_eq!(falling_factorial(5, 0), Some(1));
+        assert_eq!(falling_factorial(5, 1), Some(5));
+        assert_eq!(falling_factorial(5, 5), Some(120));
+        assert_eq!(falling_factorial(5, 6), Some(0));
+        assert_eq!(falling_factorial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(falling_factorial(u64::MAX, 2), None);
+
+        assert_eq!(rising_factorial(0, 0), Some(1));
+        assert_eq!(rising_factorial(5, 0), Some(1));
+        assert_eq!(rising_factorial(1, 5), Some(120));
+        assert_eq!(rising_factorial(3, 3), Some(60));
+        assert_eq!(rising_factorial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(rising_factorial(u64::MAX, 2), None);
+
+        for n in 0..15 {
+            for k in 0..(n + 1) {
+                assert_eq!(falling_factorial(n, k),
+                           rising_factorial(n - k + 1, k));
+            }
+        }
+    }
+
+#[test]
+    fn t_binomial() {
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(5, 5), Some(1));
+        assert_eq!(binomial(5, 6), Some(0));
+        assert_eq!(binomial(10, 3), Some(120));
+        assert_eq!(binomial(10, 7), Some(120));
+        assert_eq!(binomial(52, 5), Some(2_598_960));
+        assert_eq!(binomial(62, 31), Some(465_428_353_255_261_088));
+        assert_eq!(binomial(67, 33), Some(14_226_520_737_620_288_370));
+        assert_eq!(binomial(68, 34), None);
+        assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, 2), None);
+
+        for n in 1..30 {
+            for k in 1..n {
+                assert_eq!(binomial(n, k).unwrap(),
+                           binomial(n - 1, k - 1).unwrap() +
+                           binomial(n - 1, k).unwrap());
+            }
+        }
+    }
+
+#[test]
+    fn t_bernoulli() {
+        assert_eq!(bernoulli(0), (1, 1));
+        assert_eq!(bernoulli(1), (-1, 2));
+        assert_eq!(bernoulli(2), (1, 6));
+        assert_eq!(bernoulli(3), (0, 1));
+        assert_eq!(bernoulli(4), (-1, 30));
+        assert_eq!(bernoulli(6), (1, 42));
+        assert_eq!(bernoulli(8), (-1, 30));
+        assert_eq!(bernoulli(10), (5, 66));
+        assert_eq!(bernoulli(12), (-691, 2730));
+        assert_eq!(bernoulli(20), (-174_611, 330));
+        assert_eq!(bernoulli(34), (2_577_687_858_367, 6));
+        assert_eq!(bernoulli(101), (0, 1));
+    }
+
+#[test]
+#[should_panic]
+    fn t_bernoulli_panic() {
+        bernoulli(36);
+    }
+
+#[test]
+    fn t_smarandache() {
+        assert_eq!(smarandache(1), 1);
+        assert_eq!(smarandache(2), 2);
+        assert_eq!(smarandache(4), 4);
+        assert_eq!(smarandache(6), 3);
+        assert_eq!(smarandache(8), 4);
+        assert_eq!(smarandache(9), 6);
+        assert_eq!(smarandache(16), 6);
+        assert_eq!(smarandache(24), 4);
+        assert_eq!(smarandache(1 << 63), 64);
+        assert_eq!(smarandache(9_223_372_036_854_775_807), 649_657);
+
+        for p in [2, 3, 5, 7, 97, 65_537, 4_294_967_291] {
+            assert_eq!(smarandache(p), p);
+        }
+
+        for n in 1..500 {
+            let m = smarandache(n);
+            let mut fac = 1;
+            for i in 1..(m + 1) {
+                assert!(i == 1 || fac % n != 0);
+                fac = fac * i % n;
+            }
+            assert_eq!(fac % n, 0);
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_smarandache_panic() {
+        smarandache(0);
+    }
+}
@@ -1,11 +1,18 @@
 //! Module for working with continued fractions.
 //!
 //! This module has functions for generating continued fraction
-//! representations of square roots and `e`, and functions for
+//! representations of square roots, higher roots, and `e`, including
+//! repeated periods of square roots, and functions for
 //! expanding continued fractions into simple fractions and floating
-//! point formats.
+//! point formats, along with generalized continued fractions, which
+//! have numerators other than one.
 
 use std::mem;
+use super::digits;
+use super::rational::Rational;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Type alias for continued fractions.
 ///
@@ -58,6 +65,168 @@ pub fn square_root(x: u64) -> ContinuedFraction {
     expansion
 }
 
+/// Return a `ContinuedFraction` of the leading term of the square root
+/// of `x`, followed by `n` copies of its period.
+///
+/// `square_root()` only returns a single period, this function repeats
+/// it, which is useful for expanding the fraction to a greater accuracy
+/// with the `expand_*()` functions, as they treat their argument as
+/// a finite continued fraction.
+///
+/// The period of the square root of a non-square always ends with
+/// twice the leading term, so `floor(sqrt(x)) + sqrt(x)` has a purely
+/// periodic continued fraction, while the square root itself never
+/// does.
+///
+/// If `x` is a perfect square, the continued fraction has no period,
+/// and only its square root is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::sqrt_expansion_full;
+/// assert_eq!(sqrt_expansion_full(23, 2), vec![4, 1, 3, 1, 8, 1, 3, 1, 8]);
+/// assert_eq!(sqrt_expansion_full(25, 2), vec![5]);
+/// ```
+pub fn sqrt_expansion_full(x: u64, n: usize) -> ContinuedFraction {
+    let fraction = square_root(x);
+
+    let mut expansion: ContinuedFraction = 
+        Vec::with_capacity(1 + (fraction.len() - 1) * n);
+    expansion.push(fraction[0]);
+    for _ in 0..n {
+        expansion.extend_from_slice(&fraction[1..]);
+    }
+
+    expansion
+}
+
+/// Return a `ContinuedFraction` of the first `terms` terms of the
+/// continued fraction of the cube root of `x`.
+///
+/// This is a helper function that calls `nth_root()` with `k = 3`,
+/// see `nth_root()` for more information.
+///
+/// # Panics
+///
+/// Panics if `nth_root()` panics, see the documentation for this
+/// function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::cube_root;
+/// assert_eq!(cube_root(2, 10), vec![1, 3, 1, 5, 1, 1, 4, 1, 1, 8]);
+/// assert_eq!(cube_root(27, 10), vec![3]);
+/// ```
+pub fn cube_root(x: u64, terms: usize) -> ContinuedFraction {
+    nth_root(x, 3, terms)
+}
+
+/// Return a `ContinuedFraction` of the first `terms` terms of the
+/// continued fraction of the `k`th root of `x`.
+///
+/// Unlike square roots, the continued fractions of higher roots are
+/// not periodic, so the terms are found one at a time, and exactly,
+/// from the polynomial `t^k - x`. At each step the next term `a` is
+/// the integer part of the polynomial's root greater than one, and the
+/// polynomial is replaced by one whose root is `1 / (root - a)`, that
+/// is, `t^k * p(a + 1 / t)`.
+///
+/// If `x` is a perfect `k`th power, the continued fraction is finite,
+/// and only its root is returned.
+///
+/// The coefficients of the polynomials grow quickly, so only a few
+/// dozen terms can be computed for small values of `x` and `k`.
+///
+/// # Panics
+///
+/// Panics if `k` or `terms` is zero.
+///
+/// Panics if the coefficients of the polynomial are too large for 
+/// an `i128`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::{nth_root, expand_f64};
+/// assert_eq!(nth_root(2, 2, 5), vec![1, 2, 2, 2, 2]);
+/// assert_eq!(nth_root(32, 5, 5), vec![2]);
+/// assert!((expand_f64(&nth_root(5, 4, 15)) - 5f64.powf(0.25)).abs() < 1e-12);
+/// ```
+pub fn nth_root(x: u64, k: u32, terms: usize) -> ContinuedFraction {
+    assert!(k != 0, "cannot take the zeroth root of {}!", x);
+    assert!(terms != 0, "cannot produce continued fraction of zero length!");
+
+    let overflow = || -> ! { 
+        panic!("continued fraction term {} of the root of {} is too large!",
+               terms, x)
+    };
+
+    // coefficients of t^k - x, in increasing order of degree
+    let mut poly: Vec<i128> = vec![0; k as usize + 1];
+    poly[0] = -(x as i128);
+    poly[k as usize] = 1;
+
+    let mut fraction: ContinuedFraction = Vec::with_capacity(terms);
+    while fraction.len() < terms {
+        let a = root_floor(&poly).unwrap_or_else(|| overflow());
+        fraction.push(a as u64);
+
+        // shift the polynomial to p(t + a) with synthetic division
+        for i in 0..poly.len() {
+            for j in (i..(poly.len() - 1)).rev() {
+                poly[j] = poly[j + 1].checked_mul(a)
+                                     .and_then(|v| v.checked_add(poly[j]))
+                                     .unwrap_or_else(|| overflow());
+            }
+        }
+
+        // the root was an integer
+        if poly[0] == 0 {
+            break;
+        }
+
+        // reverse to get t^k * p(a + 1 / t), with a positive lead
+        poly.reverse();
+        if poly[k as usize] < 0 {
+            for c in poly.iter_mut() {
+                *c = -*c;
+            }
+        }
+    }
+
+    fraction
+}
+
+// helper function to find the largest integer `a` with p(a) <= 0, where
+// p has a positive leading coefficient and a single positive root,
+// returning None if the values are too large for an i128
+fn root_floor(poly: &[i128]) -> Option<i128> {
+    let eval = |t: i128| -> Option<i128> {
+        poly.iter().rev().try_fold(0i128, |acc, c| {
+            acc.checked_mul(t)?.checked_add(*c)
+        })
+    };
+
+    let mut hi: i128 = 1;
+    while eval(hi)? <= 0 {
+        hi = hi.checked_mul(2)?;
+    }
+
+    let mut lo = hi / 2;
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if eval(mid)? <= 0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Some(lo)
+}
+
 /// Return a `ContinuedFraction` of the continued fraction representing
 /// `e` to `n` terms.
 ///
@@ -99,6 +268,113 @@ pub fn e(n: u64) -> ContinuedFraction {
     frac
 }
 
+/// Return the finite continued fraction of the rational number
+/// `num / den`.
+///
+/// The terms are found with the Euclidean algorithm, and the last
+/// term is never one unless the fraction is equal to one, so every
+/// rational number has exactly one representation.
+///
+/// # Panics
+///
+/// Panics if `den` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::from_rational;
+/// assert_eq!(from_rational(3, 5), vec![0, 1, 1, 2]);
+/// assert_eq!(from_rational(415, 93), vec![4, 2, 6, 7]);
+/// ```
+pub fn from_rational(mut num: u64, mut den: u64) -> ContinuedFraction {
+    assert!(den != 0, "denominator cannot be zero!");
+
+    let mut fraction = ContinuedFraction::new();
+    while den != 0 {
+        fraction.push(num / den);
+        num %= den;
+        mem::swap(&mut num, &mut den);
+    }
+
+    fraction
+}
+
+/// Return the canonical form of the finite continued fraction
+/// `fraction`.
+///
+/// A finite continued fraction has more than one representation,
+/// as a last term of one can be added to the term before it, and
+/// a zero term can be removed by adding the terms on either side of
+/// it together:
+///
+/// ```text
+/// [a; ..., n, 1] = [a; ..., n + 1]
+/// [a; ..., m, 0, n, ...] = [a; ..., m + n, ...]
+/// ```
+///
+/// The canonical form has no zero terms after the first, and never
+/// ends in one unless it is the whole fraction, which is the form
+/// returned by `from_rational()`.
+///
+/// # Panics
+///
+/// Panics if `fraction` is empty, or if it ends in a zero term after
+/// the first, as such a fraction has no value.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::canonicalize;
+/// assert_eq!(canonicalize(&vec![1, 2, 1]), vec![1, 3]);
+/// assert_eq!(canonicalize(&vec![1, 2, 0, 3]), vec![1, 5]);
+/// ```
+pub fn canonicalize(fraction: &ContinuedFraction) -> ContinuedFraction {
+    assert!(!fraction.is_empty(), 
+            "cannot canonicalize empty continued fraction!");
+
+    let mut canonical: ContinuedFraction = vec![fraction[0]];
+    let mut i = 1;
+    while i < fraction.len() {
+        if fraction[i] == 0 {
+            assert!(i + 1 < fraction.len(), 
+                    "continued fraction cannot end in a zero term!");
+            *canonical.last_mut().unwrap() += fraction[i + 1];
+            i += 2;
+        } else {
+            canonical.push(fraction[i]);
+            i += 1;
+        }
+    }
+
+    if canonical.len() > 1 && *canonical.last().unwrap() == 1 {
+        canonical.pop();
+        *canonical.last_mut().unwrap() += 1;
+    }
+
+    canonical
+}
+
+/// Return `true` if the finite continued fractions `a` and `b`
+/// represent the same value.
+///
+/// This is done by comparing the canonical forms of both fractions,
+/// see the documentation of `canonicalize()` for more information.
+///
+/// # Panics
+///
+/// Panics if `canonicalize()` panics for either fraction.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::equivalent;
+/// assert_eq!(equivalent(&vec![1, 2, 1], &vec![1, 3]), true);
+/// assert_eq!(equivalent(&vec![1, 2], &vec![1, 3]), false);
+/// ```
+pub fn equivalent(a: &ContinuedFraction, b: &ContinuedFraction) -> bool {
+    canonicalize(a) == canonicalize(b)
+}
+
 /// Expand the continued fraction `fraction` `n` times, storing
 /// the result as a fraction in a double tuple of `u64`.
 ///
@@ -176,6 +452,34 @@ pub fn expand_fraction(fraction: &ContinuedFraction) -> (u64, u64) {
     expand_fraction_ntimes(fraction, 1)
 }
 
+/// Expand the continued fraction `fraction` one time, storing
+/// the result as a `Rational`.
+///
+/// This is a helper function that calls `expand_fraction()`, and
+/// converts the result into a `Rational`. See the documentation for
+/// `expand_fraction_ntimes()` for more information.
+///
+/// # Panics
+/// 
+/// Panics if `expand_fraction_ntimes()` panics, or if the numerator
+/// or denominator is larger than `i64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::expand_rational;
+/// use reikna::rational::Rational;
+/// assert_eq!(expand_rational(&vec![2, 1]), Rational::new(5, 2));
+/// assert_eq!(expand_rational(&vec![3]).to_string(), "3");
+/// ```
+pub fn expand_rational(fraction: &ContinuedFraction) -> Rational {
+    let (num, den) = expand_fraction(fraction);
+    assert!(num <= i64::MAX as u64 && den <= i64::MAX as u64,
+            "expanded fraction {}/{} is too large for a Rational!", num, den);
+
+    Rational::new(num as i64, den as i64)
+}
+
 /// Expand the continued fraction `fraction` `n` times, storing
 /// the result as an `f64`.
 ///
@@ -262,6 +566,183 @@ pub fn expand_f64(fraction: &ContinuedFraction) -> f64 {
     expand_f64_ntimes(fraction, 1)
 }
 
+/// A generalized continued fraction, with numerators other than one.
+///
+/// This represents the value
+///
+/// ```text
+/// b0 + a1 / (b1 + a2 / (b2 + a3 / (b3 + ...)))
+/// ```
+///
+/// where `a` holds the numerators `a1, a2, a3, ...`, and `b` holds the
+/// denominators `b0, b1, b2, ...`, including the initial term `b0`.
+/// A `ContinuedFraction` is the case where every numerator is one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeneralizedCF {
+    /// The numerators `a1, a2, a3, ...`.
+    pub a: Vec<u64>,
+    /// The initial term and denominators `b0, b1, b2, ...`.
+    pub b: Vec<u64>,
+}
+
+impl GeneralizedCF {
+    /// Return the first `terms` numerators and denominators of the
+    /// generalized continued fraction
+    ///
+    /// ```text
+    /// pi = 4 / (1 + 1^2 / (3 + 2^2 / (5 + 3^2 / (7 + ...))))
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::continued_fraction::GeneralizedCF;
+    /// let pi = GeneralizedCF::pi(4);
+    /// assert_eq!(pi.a, vec![4, 1, 4, 9]);
+    /// assert_eq!(pi.b, vec![0, 1, 3, 5, 7]);
+    /// ```
+    pub fn pi(terms: usize) -> GeneralizedCF {
+        let mut a: Vec<u64> = Vec::with_capacity(terms);
+        let mut b: Vec<u64> = Vec::with_capacity(terms + 1);
+        b.push(0);
+
+        for k in 0..(terms as u64) {
+            a.push(if k == 0 { 4 } else { k * k });
+            b.push(2 * k + 1);
+        }
+
+        GeneralizedCF { a, b }
+    }
+}
+
+/// Expand the first `n` terms of the generalized continued fraction
+/// `cf`, storing the result as an `f64`.
+///
+/// The value is computed from the front using the fundamental
+/// recurrence formulas
+///
+/// ```text
+/// A(k) = b(k) * A(k - 1) + a(k) * A(k - 2)
+/// B(k) = b(k) * B(k - 1) + a(k) * B(k - 2)
+/// ```
+///
+/// giving the convergent `A(n) / B(n)`. The numerators and denominators
+/// are rescaled at each step, so they do not overflow even when many
+/// terms are expanded.
+///
+/// If `n` is zero, the initial term `b0` is returned.
+///
+/// # Panics
+///
+/// Panics if `cf` has less than `n` numerators or less than `n + 1`
+/// denominators.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::{expand_generalized, GeneralizedCF};
+/// let pi = GeneralizedCF::pi(20);
+/// assert!((expand_generalized(&pi, 20) - std::f64::consts::PI).abs() < 1e-14);
+/// ```
+pub fn expand_generalized(cf: &GeneralizedCF, n: usize) -> f64 {
+    assert!(cf.a.len() >= n && cf.b.len() > n,
+            "cannot expand {} terms of a shorter continued fraction!", n);
+
+    let (mut num_old, mut num) = (1f64, cf.b[0] as f64);
+    let (mut den_old, mut den) = (0f64, 1f64);
+    for k in 1..(n + 1) {
+        let a = cf.a[k - 1] as f64;
+        let b = cf.b[k] as f64;
+
+        let num_new = b * num + a * num_old;
+        let den_new = b * den + a * den_old;
+        num_old = num;
+        den_old = den;
+        num = num_new;
+        den = den_new;
+
+        if den != 0.0 {
+            num_old /= den;
+            den_old /= den;
+            num /= den;
+            den = 1.0;
+        }
+    }
+
+    num / den
+}
+
+/// Return the best rational approximation to `x` with a denominator no
+/// greater than `max_denominator`, as a fraction in a double tuple of `u64`.
+///
+/// The result tuple is formatted as:
+///
+/// ```text
+/// (numerator, denominator)
+/// ```
+///
+/// This function works by computing the convergents of the continued
+/// fraction of `x` until the denominators become too large, then
+/// checking if a semiconvergent between the last two convergents is
+/// a closer approximation than the last convergent.
+///
+/// # Panics
+///
+/// Panics if `x` is negative, infinite, or NaN, if `x` is `2^64` or
+/// larger, or if `max_denominator` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::best_rational;
+/// use std::f64::consts::PI;
+/// assert_eq!(best_rational(PI, 10), (22, 7));
+/// assert_eq!(best_rational(PI, 200), (355, 113));
+/// ```
+pub fn best_rational(x: f64, max_denominator: u64) -> (u64, u64) {
+    assert!(x >= 0.0 && x.is_finite() && x < u64::MAX as f64,
+            "cannot approximate {} with a positive rational!", x);
+    assert!(max_denominator != 0, "max denominator must be positive!");
+
+    let (mut h0, mut h1): (u64, u64) = (0, 1);
+    let (mut k0, mut k1): (u64, u64) = (1, 0);
+
+    let mut y = x;
+    loop {
+        let a = y.floor() as u64;
+
+        let h2 = a.checked_mul(h1).and_then(|h| h.checked_add(h0));
+        let k2 = a.checked_mul(k1).and_then(|k| k.checked_add(k0));
+        let (h2, k2) = match (h2, k2) {
+            (Some(h), Some(k)) if k <= max_denominator => (h, k),
+            _ => break,
+        };
+
+        h0 = h1;
+        h1 = h2;
+        k0 = k1;
+        k1 = k2;
+
+        let frac = y - y.floor();
+        if frac < f64::EPSILON {
+            return (h1, k1);
+        }
+        y = 1.0 / frac;
+    }
+
+    let t = (max_denominator - k0) / k1;
+    let semi = t.checked_mul(h1).and_then(|h| h.checked_add(h0));
+    if let Some(h) = semi {
+        let k = k0 + t * k1;
+        if (x - h as f64 / k as f64).abs() < (x - h1 as f64 / k1 as f64).abs() {
+            return (h, k);
+        }
+    }
+
+    (h1, k1)
+}
+
 
 /// Return a nicely formatted `String` of the continued fraction
 /// `fraction.
@@ -281,19 +762,49 @@ pub fn expand_f64(fraction: &ContinuedFraction) -> f64 {
 /// [17]
 /// ```
 pub fn to_string(fraction: &ContinuedFraction) -> String {
+    to_string_radix(fraction, 10)
+}
+
+/// Return a nicely formatted `String` of the continued fraction
+/// `fraction`, with each term written in base `radix`.
+///
+/// This function works in the same way as `to_string()`, but the
+/// terms are converted with `digits::to_base()`. Digits larger than
+/// nine are written as lowercase letters.
+///
+/// # Panics
+///
+/// Panics if `radix` is less than `2` or greater than `36`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::continued_fraction::to_string_radix;
+/// assert_eq!(to_string_radix(&vec![10, 15, 255], 16), "[a; f, ff]");
+/// assert_eq!(to_string_radix(&vec![1, 2, 3], 2), "[1; 10, 11]");
+/// ```
+pub fn to_string_radix(fraction: &ContinuedFraction, radix: u32) -> String {
+    assert!((2..=36).contains(&radix), "radix {} is not in [2, 36]!", radix);
+
+    let term = |n: u64| -> String {
+        let digits = digits::to_base(n, radix as u64);
+        digits.iter().map(|d| char::from_digit(*d as u32, radix).unwrap())
+                     .collect()
+    };
+
     if fraction.is_empty() {
         return "[]".to_string();
     }
 
     let mut string = "[".to_string();
-    string.push_str(&fraction[0].to_string());
+    string.push_str(&term(fraction[0]));
     if fraction.len() != 1 {
         string.push_str("; ");
         for i in 1..fraction.len() {
             if i != 1 {
                 string.push_str(", ");
             }
-            string.push_str(&fraction[i].to_string());
+            string.push_str(&term(fraction[i]));
         }
     }
     string.push_str("]");
@@ -318,6 +829,99 @@ mod tests {
         assert_eq!(square_root(20), vec![4, 2, 8]);
     }
 
+#[test]
+    fn t_sqrt_expansion_full() {
+        assert_eq!(sqrt_expansion_full(23, 0), vec![4]);
+        assert_eq!(sqrt_expansion_full(23, 1), vec![4, 1, 3, 1, 8]);
+        assert_eq!(sqrt_expansion_full(23, 2), vec![4, 1, 3, 1, 8, 1, 3, 1, 8]);
+        assert_eq!(sqrt_expansion_full(2, 4), vec![1, 2, 2, 2, 2]);
+        assert_eq!(sqrt_expansion_full(0, 3), vec![0]);
+        assert_eq!(sqrt_expansion_full(25, 3), vec![5]);
+
+        for x in 2..200 {
+            let fraction = square_root(x);
+            let full = sqrt_expansion_full(x, 3);
+            assert_eq!(full.len(), 1 + (fraction.len() - 1) * 3);
+            assert_eq!(&full[..fraction.len()], &fraction[..]);
+        }
+
+        // floor(sqrt(x)) + sqrt(x) is purely periodic
+        for x in 2..200 {
+            let mut full = sqrt_expansion_full(x, 2);
+            if full.len() == 1 {
+                continue;
+            }
+            full[0] *= 2;
+            let period = (full.len() - 1) / 2;
+            assert_eq!(full[0], full[period]);
+            assert_eq!(full[..period], full[period..(2 * period)]);
+        }
+
+        let approx = expand_f64(&sqrt_expansion_full(23, 6));
+        assert!((approx - 23f64.sqrt()).abs() < 1.0e-12);
+        let approx = expand_f64(&sqrt_expansion_full(23, 1));
+        assert!((approx - 23f64.sqrt()).abs() > 1.0e-6);
+    }
+
+#[test]
+    fn t_nth_root() {
+        let cbrt_2 = vec![1, 3, 1, 5, 1, 1, 4, 1, 1, 8, 1, 14, 1, 10, 2, 1, 
+                          4, 12, 2, 3, 2, 1, 3, 4, 1, 1, 2, 14, 3];
+        assert_eq!(cube_root(2, 10), cbrt_2[..10].to_vec());
+        assert_eq!(cube_root(2, cbrt_2.len()), cbrt_2);
+        assert!((expand_f64(&cube_root(2, 10)) - 1.259921).abs() < 1.0e-6);
+        assert!((expand_f64(&cube_root(2, 20)) - 2f64.cbrt()).abs() < 1.0e-14);
+
+        assert_eq!(cube_root(0, 5), vec![0]);
+        assert_eq!(cube_root(1, 5), vec![1]);
+        assert_eq!(cube_root(8, 5), vec![2]);
+        assert_eq!(cube_root(7, 1), vec![1]);
+        assert_eq!(nth_root(10, 1, 5), vec![10]);
+        assert_eq!(nth_root(1 << 60, 60, 5), vec![2]);
+        assert_eq!(nth_root(u64::MAX, 1, 5), vec![u64::MAX]);
+        assert_eq!(nth_root(u64::MAX, 64, 1), vec![1]);
+
+        for x in 2..100 {
+            assert_eq!(nth_root(x, 2, 12), sqrt_expansion_full(x, 12)
+                                               .into_iter()
+                                               .take(12)
+                                               .collect::<Vec<u64>>());
+
+            // the first few terms can be found with floating point
+            for k in 3..6 {
+                let fraction = nth_root(x, k, 6);
+                if fraction.len() == 1 {
+                    assert_eq!(fraction[0].pow(k), x);
+                    continue;
+                }
+
+                let mut y = (x as f64).powf(1.0 / k as f64);
+                for term in fraction {
+                    assert_eq!(term, y.floor() as u64);
+                    y = 1.0 / (y - y.floor());
+                }
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_nth_root_panic() {
+        nth_root(2, 0, 5);
+    }
+
+#[test]
+#[should_panic]
+    fn t_nth_root_panic_2() {
+        cube_root(2, 0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_nth_root_panic_3() {
+        cube_root(2, 1_000);
+    }
+
 #[test]
     fn t_e() {
         assert_eq!(e(1),  vec![2]);
@@ -341,6 +945,94 @@ mod tests {
         assert_eq!(expand_fraction_ntimes(&square_root(5), 1), (38, 17));
     }
 
+#[test]
+    fn t_from_rational() {
+        assert_eq!(from_rational(0, 1), vec![0]);
+        assert_eq!(from_rational(1, 1), vec![1]);
+        assert_eq!(from_rational(7, 1), vec![7]);
+        assert_eq!(from_rational(1, 7), vec![0, 7]);
+        assert_eq!(from_rational(3, 5), vec![0, 1, 1, 2]);
+        assert_eq!(from_rational(6, 10), vec![0, 1, 1, 2]);
+        assert_eq!(from_rational(415, 93), vec![4, 2, 6, 7]);
+        assert_eq!(from_rational(u64::MAX, 1), vec![u64::MAX]);
+
+        for num in 0..50 {
+            for den in 1..50 {
+                let fraction = from_rational(num, den);
+                assert!(fraction.len() == 1 || *fraction.last().unwrap() > 1);
+
+                let mut r = Rational::from(*fraction.last().unwrap() as i64);
+                for a in fraction.iter().rev().skip(1) {
+                    r = Rational::from(*a as i64) + r.recip();
+                }
+                assert_eq!(r, Rational::new(num as i64, den as i64));
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_from_rational_panic() {
+        from_rational(1, 0);
+    }
+
+#[test]
+    fn t_canonicalize() {
+        assert_eq!(canonicalize(&vec![0]), vec![0]);
+        assert_eq!(canonicalize(&vec![1]), vec![1]);
+        assert_eq!(canonicalize(&vec![0, 1]), vec![1]);
+        assert_eq!(canonicalize(&vec![1, 2, 1]), vec![1, 3]);
+        assert_eq!(canonicalize(&vec![1, 3]), vec![1, 3]);
+        assert_eq!(canonicalize(&vec![1, 2, 0, 3]), vec![1, 5]);
+        assert_eq!(canonicalize(&vec![1, 0, 1]), vec![2]);
+        assert_eq!(canonicalize(&vec![4, 2, 6, 6, 1]), vec![4, 2, 6, 7]);
+
+        assert_eq!(equivalent(&vec![1, 2, 1], &vec![1, 3]), true);
+        assert_eq!(equivalent(&vec![1, 3], &vec![1, 2, 1]), true);
+        assert_eq!(equivalent(&vec![2, 0, 3, 1], &vec![6]), true);
+        assert_eq!(equivalent(&vec![1, 2], &vec![1, 3]), false);
+
+        for num in 0..50 {
+            for den in 1..50 {
+                let fraction = from_rational(num, den);
+                assert_eq!(canonicalize(&fraction), fraction);
+
+                let mut longer = fraction.clone();
+                if longer.len() > 1 || longer[0] > 0 {
+                    *longer.last_mut().unwrap() -= 1;
+                    longer.push(1);
+                    assert_eq!(canonicalize(&longer), fraction);
+                    assert!(equivalent(&longer, &fraction));
+                }
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_canonicalize_panic() {
+        canonicalize(&vec![]);
+    }
+
+#[test]
+#[should_panic]
+    fn t_canonicalize_panic_2() {
+        canonicalize(&vec![1, 2, 0]);
+    }
+
+#[test]
+    fn t_expand_rational() {
+        assert_eq!(expand_rational(&vec![3]), Rational::from(3));
+        assert_eq!(expand_rational(&vec![2, 1]), Rational::new(5, 2));
+        assert_eq!(expand_rational(&square_root(2)), Rational::new(7, 5));
+
+        for n in 1..20 {
+            let (num, den) = expand_fraction(&e(n));
+            assert_eq!(expand_rational(&e(n)), 
+                       Rational::new(num as i64, den as i64));
+        }
+    }
+
 #[test]
 #[should_panic]
     fn t_expand_fraction_panic() {
@@ -374,10 +1066,140 @@ mod tests {
         expand_f64_ntimes(&vec![1, 2], 0);
     }
 
+#[test]
+    fn t_expand_generalized() {
+        let pi = GeneralizedCF::pi(0);
+        assert_eq!(pi, GeneralizedCF { a: vec![], b: vec![0] });
+        assert_eq!(expand_generalized(&pi, 0), 0.0);
+
+        let pi = GeneralizedCF::pi(500);
+        assert_eq!(expand_generalized(&pi, 1), 4.0);
+        assert_eq!(expand_generalized(&pi, 2), 3.0);
+        assert_fp!(expand_generalized(&pi, 3), 19.0 / 6.0, 1e-15);
+
+        let exact = ::std::f64::consts::PI;
+        let mut last_error = f64::INFINITY;
+        for n in 1..16 {
+            let error = (expand_generalized(&pi, n) - exact).abs();
+            assert!(error < last_error);
+            last_error = error;
+        }
+        assert_fp!(expand_generalized(&pi, 20), exact, 1e-14);
+        assert_fp!(expand_generalized(&pi, 100), exact, 1e-14);
+        assert_fp!(expand_generalized(&pi, 500), exact, 1e-13);
+
+        // every numerator one gives a simple continued fraction
+        let cf = GeneralizedCF { a: vec![1; 3], b: from_rational(415, 93) };
+        assert_eq!(expand_generalized(&cf, 0), 4.0);
+        assert_eq!(expand_generalized(&cf, 1), 4.5);
+        assert_fp!(expand_generalized(&cf, 2), 58.0 / 13.0, 1e-14);
+        assert_fp!(expand_generalized(&cf, 3), 415.0 / 93.0, 1e-14);
+
+        // sqrt(5) = 1 + 4 / (2 + 4 / (2 + 4 / (2 + ...)))
+        let mut cf = GeneralizedCF { a: vec![4; 60], b: vec![2; 61] };
+        cf.b[0] = 1;
+        assert_fp!(expand_generalized(&cf, 60), 5f64.sqrt(), 1e-14);
+    }
+
+#[test]
+#[should_panic]
+    fn t_expand_generalized_panic() {
+        expand_generalized(&GeneralizedCF::pi(5), 6);
+    }
+
+#[test]
+#[should_panic]
+    fn t_expand_generalized_panic_2() {
+        let cf = GeneralizedCF { a: vec![1, 1], b: vec![1, 2] };
+        expand_generalized(&cf, 2);
+    }
+
+#[test]
+    fn t_best_rational() {
+        use std::f64::consts::{PI, E};
+
+        assert_eq!(best_rational(PI, 1), (3, 1));
+        assert_eq!(best_rational(PI, 10), (22, 7));
+        assert_eq!(best_rational(PI, 100), (311, 99));
+        assert_eq!(best_rational(PI, 200), (355, 113));
+        assert_eq!(best_rational(PI, 10_000), (355, 113));
+        assert_eq!(best_rational(PI, 30_000), (94_053, 29_938));
+        assert_eq!(best_rational(E, 1_000), (1_457, 536));
+        assert_eq!(best_rational(0.0, 10), (0, 1));
+        assert_eq!(best_rational(0.5, 10), (1, 2));
+        assert_eq!(best_rational(0.5, 1), (0, 1));
+        assert_eq!(best_rational(0.75, 1), (1, 1));
+        assert_eq!(best_rational(17.0, 10), (17, 1));
+        assert_eq!(best_rational(0.1, 1_000_000), (1, 10));
+
+        for den in 1..200 {
+            let (p, q) = best_rational(2f64.sqrt(), den);
+            assert!(q <= den);
+            for k in 1..(den + 1) {
+                let closest = (2f64.sqrt() * k as f64).round() / k as f64;
+                assert!((2f64.sqrt() - p as f64 / q as f64).abs() <= 
+                        (2f64.sqrt() - closest).abs());
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_best_rational_panic() {
+        best_rational(-1.0, 10);
+    }
+
+#[test]
+#[should_panic]
+    fn t_best_rational_panic_2() {
+        best_rational(1.0, 0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_best_rational_panic_3() {
+        best_rational(1e20, 10);
+    }
+
+#[test]
+#[should_panic]
+    fn t_best_rational_panic_4() {
+        best_rational(u64::MAX as f64, 10);
+    }
+
 #[test]
     fn t_to_string() {
         assert_eq!(to_string(&vec![]), "[]".to_string());
         assert_eq!(to_string(&vec![17]), "[17]".to_string());
         assert_eq!(to_string(&vec![1, 2, 3]), "[1; 2, 3]".to_string());
     }
+
+#[test]
+    fn t_to_string_radix() {
+        assert_eq!(to_string_radix(&vec![], 16), "[]".to_string());
+        assert_eq!(to_string_radix(&vec![0], 2), "[0]".to_string());
+        assert_eq!(to_string_radix(&vec![10, 15, 255], 16), 
+                   "[a; f, ff]".to_string());
+        assert_eq!(to_string_radix(&vec![1, 2, 3, 4], 2), 
+                   "[1; 10, 11, 100]".to_string());
+        assert_eq!(to_string_radix(&vec![35, 36], 36), "[z; 10]".to_string());
+        assert_eq!(to_string_radix(&vec![u64::MAX], 16), 
+                   "[ffffffffffffffff]".to_string());
+
+        for n in 1..20 {
+            assert_eq!(to_string_radix(&e(n), 10), to_string(&e(n)));
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_to_string_radix_panic() {
+        to_string_radix(&vec![1, 2], 1);
+    }
+
+#[test]
+#[should_panic]
+    fn t_to_string_radix_panic_2() {
+        to_string_radix(&vec![1, 2], 37);
+    }
 }
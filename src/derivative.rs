@@ -1,11 +1,15 @@
 //! Module for working with derivatives.
 //!
 //! This module has functions for estimating and evaluating
-//! derivatives of functions and for computing the slope and
-//! concavity of functions at single points.
+//! derivatives of functions, for computing the slope and
+//! concavity of functions at single points, and for finding
+//! the extrema, inflection points, and roots of functions.
 
 pub use super::func::*;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// The value used for `h` in derivative estimates.
 ///
 /// This value is chosen so as to offer the best accuracy, 
@@ -171,6 +175,184 @@ pub fn slope_at(f: &Function, x: f64) -> f64 {
     (f(x + EPSILON) - f(x - EPSILON)) / (EPSILON * 2.0)
 }
 
+/// Return a step size for estimating derivatives at `x`, scaled
+/// to the magnitude of `x`.
+///
+/// A fixed step like `EPSILON` is too small relative to very large
+/// values of `x`, where floating point rounding dominates the estimate.
+/// For central differences, a good step size is
+///
+/// ```text
+/// h = cbrt(machine epsilon) * max(|x|, 1)
+/// ```
+///
+/// which balances the truncation error against the rounding error.
+/// The step is not scaled down for `|x| < 1`, so every such `x` uses
+/// the same step as `x = 1`, about `6e-6`, which is larger than
+/// `EPSILON`.
+///
+/// Examples
+///
+/// ```
+/// use reikna::derivative::*;
+/// assert_eq!(optimal_h(0.0), optimal_h(1.0));
+/// assert!(optimal_h(1.0e6) > optimal_h(1.0));
+/// ```
+pub fn optimal_h(x: f64) -> f64 {
+    f64::EPSILON.cbrt() * x.abs().max(1.0)
+}
+
+/// Estimate the value of the derivative of `f` at `x`, using
+/// a step size chosen by `optimal_h()`.
+///
+/// This function works in the same way as `slope_at()`, but uses
+/// `optimal_h(x)` rather than `EPSILON` for `h`, making it more accurate
+/// for values of `x` that are very large. See the documentation for
+/// `optimal_h()` for more information.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x.ln()];
+/// println!("f'(1e6) = {}", slope_at(&f, 1.0e6));
+/// println!("f'(1e6) = {}", slope_at_auto(&f, 1.0e6));
+/// # }
+///
+/// ```
+/// Outputs:
+///
+/// ```text
+/// f'(1e6) = 0.000001000088900582341
+/// f'(1e6) = 0.0000010000000000235538
+/// ```
+pub fn slope_at_auto(f: &Function, x: f64) -> f64 {
+    let h = optimal_h(x);
+    (f(x + h) - f(x - h)) / (h * 2.0)
+}
+
+/// Return a `Vec<f64>` estimating the gradient of `f` at `point`,
+/// that is, the partial derivative of `f` with respect to each of
+/// its variables.
+///
+/// Each partial derivative is estimated with a central difference,
+/// in the same way as `slope_at_auto()`, varying a single coordinate
+/// of `point` by `optimal_h()` of that coordinate.
+///
+/// Examples
+///
+/// ```
+/// use reikna::derivative::*;
+///
+/// let f: FunctionN = Rc::new(|x: &[f64]| x[0] * x[0] + 2.0 * x[1] * x[1]);
+/// println!("grad f(1, 2) = {:?}", gradient_n(&f, &[1.0, 2.0]));
+/// ```
+///
+/// Outputs:
+///
+/// ```text
+/// grad f(1, 2) = [1.9999999999004336, 7.999999999895083]
+/// ```
+pub fn gradient_n(f: &FunctionN, point: &[f64]) -> Vec<f64> {
+    let mut x = point.to_vec();
+    let mut gradient: Vec<f64> = Vec::with_capacity(point.len());
+    for i in 0..point.len() {
+        let h = optimal_h(point[i]);
+
+        x[i] = point[i] + h;
+        let high = f(&x);
+        x[i] = point[i] - h;
+        let low = f(&x);
+        x[i] = point[i];
+
+        gradient.push((high - low) / (h * 2.0));
+    }
+
+    gradient
+}
+
+/// Return a `Vec<Vec<f64>>` estimating the Hessian of `f` at `point`,
+/// that is, the symmetric matrix of the second partial derivatives
+/// of `f`, where the element at `[i][j]` is the partial derivative
+/// with respect to variables `i` and `j`.
+///
+/// The second partials are estimated with central differences,
+///
+/// ```text
+///            f(x + h_i) - 2f(x) + f(x - h_i)
+/// f_ii(x) ~= -------------------------------
+///                         h_i^2
+///
+///            f(x + h_i + h_j) - f(x + h_i - h_j)
+///          - f(x - h_i + h_j) + f(x - h_i - h_j)
+/// f_ij(x) ~= -----------------------------------
+///                        4 * h_i * h_j
+/// ```
+///
+/// where each `h_i` is scaled to the magnitude of its coordinate, in
+/// the same way as `optimal_h()`, but with the fourth root of machine
+/// epsilon, which balances the errors of a second difference.
+///
+/// Estimating the whole matrix takes `2n^2 + 1` evaluations of `f` 
+/// for `n` variables, so the cost grows quadratically with `n`.
+///
+/// Examples
+///
+/// ```
+/// use reikna::derivative::*;
+///
+/// let f: FunctionN = Rc::new(|x: &[f64]| x[0] * x[0] * x[1]);
+/// println!("H f(1, 2) = {:?}", hessian(&f, &[1.0, 2.0]));
+/// ```
+///
+/// Outputs:
+///
+/// ```text
+/// H f(1, 2) = [[4.0, 2.0], [2.0, 0.0]]
+/// ```
+pub fn hessian(f: &FunctionN, point: &[f64]) -> Vec<Vec<f64>> {
+    let n = point.len();
+    let h: Vec<f64> = point.iter()
+                           .map(|x| f64::EPSILON.powf(0.25) * x.abs().max(1.0))
+                           .collect();
+
+    let mut x = point.to_vec();
+    let center = f(&x);
+
+    let mut hess = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        x[i] = point[i] + h[i];
+        let high = f(&x);
+        x[i] = point[i] - h[i];
+        let low = f(&x);
+        x[i] = point[i];
+
+        hess[i][i] = (high - center * 2.0 + low) / (h[i] * h[i]);
+
+        for j in 0..i {
+            let mut corner = |si: f64, sj: f64| {
+                x[i] = point[i] + si * h[i];
+                x[j] = point[j] + sj * h[j];
+                let val = f(&x);
+                x[i] = point[i];
+                x[j] = point[j];
+                val
+            };
+
+            let mixed = (corner(1.0, 1.0) - corner(1.0, -1.0)
+                       - corner(-1.0, 1.0) + corner(-1.0, -1.0))
+                      / (h[i] * h[j] * 4.0);
+            hess[i][j] = mixed;
+            hess[j][i] = mixed;
+        }
+    }
+
+    hess
+}
+
 /// Estimate the value of the second derivative of `f` at `x`
 ///
 /// This function works by applying the limit definition of
@@ -207,6 +389,417 @@ pub fn concavity_at(f: &Function, x: f64) -> f64 {
     / (EPSILON * 4.0 * EPSILON)
 }
 
+/// Return a `Vec<f64>` of the Taylor coefficients of `f` about
+/// `center`, up to and including the `order`th coefficient.
+///
+/// The `k`th coefficient is calculated as
+///
+/// ```text
+///        f^(k)(center)
+/// c_k = ---------------
+///             k!
+/// ```
+///
+/// where `f^(k)` is estimated by `nth_derivative()`. See the 
+/// documentation for `nth_derivative()` for more information.
+///
+/// Note -- because the error of each derivative estimate compounds,
+/// the coefficients quickly lose accuracy as `k` grows. In practice
+/// only the coefficients up to the second order are reliable, and
+/// coefficients past the third order are usually meaningless.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x| x * x];
+/// let coefficients = taylor_coefficients(&f, 1.0, 2);
+///
+/// println!("{:?}", coefficients);
+/// # }
+/// ```
+///
+/// Outputs:
+///
+/// ```text
+/// [1.0, 2.0000000000575113, 1.0002554340360348]
+/// ```
+pub fn taylor_coefficients(f: &Function, center: f64, order: usize) 
+                           -> Vec<f64> {
+    let mut coefficients: Vec<f64> = Vec::with_capacity(order + 1);
+    let mut factorial = 1.0;
+    for k in 0..(order + 1) {
+        if k > 1 {
+            factorial *= k as f64;
+        }
+        coefficients.push(nth_derivative(k as u64, f)(center) / factorial);
+    }
+
+    coefficients
+}
+
+/// Return a `Function` evaluating the Taylor polynomial of `f`
+/// about `center`, truncated after the `order`th term.
+///
+/// The coefficients are calculated once using `taylor_coefficients()`,
+/// see the documentation for `taylor_coefficients()` for more information,
+/// including the practical limit on `order`.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x.exp()];
+/// let poly = taylor_poly(&f, 0.0, 2);
+///
+/// println!("f(0.1)    = {}", f(0.1));
+/// println!("poly(0.1) = {}", poly(0.1));
+/// # }
+/// ```
+///
+/// Outputs:
+///
+/// ```text
+/// f(0.1)    = 1.1051709180756477
+/// poly(0.1) = 1.104999889394275
+/// ```
+pub fn taylor_poly(f: &Function, center: f64, order: usize) -> Function {
+    let coefficients = taylor_coefficients(f, center, order);
+    func!(move |x: f64| {
+        let dx = x - center;
+        coefficients.iter().rev().fold(0.0, |acc, c| acc * dx + c)
+    })
+}
+
+/// The kind of a local extremum found by `find_extrema()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExtremumKind {
+    /// A local minimum, where the function is concave up.
+    Minimum,
+    /// A local maximum, where the function is concave down.
+    Maximum,
+}
+
+/// Return a `Vec` of the local extrema of `f` in `[a, b]`, each
+/// paired with its `ExtremumKind`.
+///
+/// `[a, b]` is split into `samples` subintervals, and `slope_at()` is
+/// evaluated at the end of each one. Each subinterval where the sign of
+/// the slope changes is refined with bisection until the extremum is
+/// found to within floating point precision. Each extremum is classified
+/// as a minimum or maximum by the sign of `concavity_at()`, extrema where
+/// the concavity is zero are not included.
+///
+/// Two extrema within a single subinterval cancel out, so `samples`
+/// should be large enough that the subintervals are smaller than the
+/// distance between extrema.
+///
+/// The extrema are ordered from `a` to `b`.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x.sin()];
+/// let extrema = find_extrema(&f, 0.0, 6.0, 100);
+///
+/// println!("{:?}", extrema);
+/// # }
+/// ```
+///
+/// Outputs:
+///
+/// ```text
+/// [(1.5707963268086313, Maximum), (4.712388980314135, Minimum)]
+/// ```
+pub fn find_extrema(f: &Function, a: f64, b: f64, 
+                    samples: usize) -> Vec<(f64, ExtremumKind)> {
+    let slope = |x: f64| slope_at(f, x);
+
+    let mut extrema: Vec<(f64, ExtremumKind)> = Vec::new();
+    for x in sign_changes(&slope, a, b, samples) {
+        let concavity = concavity_at(f, x);
+        if concavity > 0.0 {
+            extrema.push((x, ExtremumKind::Minimum));
+        } else if concavity < 0.0 {
+            extrema.push((x, ExtremumKind::Maximum));
+        }
+    }
+
+    extrema
+}
+
+/// Return a `Vec<f64>` of the inflection points of `f` in `[a, b]`,
+/// that is, the points where the concavity of `f` changes sign.
+///
+/// This function works in the same way as `find_extrema()`, but looks
+/// for sign changes in `concavity_at()` rather than `slope_at()`. See
+/// the documentation for `find_extrema()` for more information.
+///
+/// As `concavity_at()` is less accurate than `slope_at()`, the points
+/// found are also less accurate than the extrema found by
+/// `find_extrema()`.
+///
+/// The inflection points are ordered from `a` to `b`.
+///
+/// # Panics
+///
+/// Panics if `samples` is zero.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x * x * x - 3.0 * x * x];
+/// let inflections = find_inflections(&f, -5.0, 5.0, 10);
+///
+/// assert_eq!(inflections.len(), 1);
+/// assert!((inflections[0] - 1.0).abs() < 1.0e-3);
+/// # }
+/// ```
+pub fn find_inflections(f: &Function, a: f64, b: f64, 
+                        samples: usize) -> Vec<f64> {
+    let concavity = |x: f64| concavity_at(f, x);
+    sign_changes(&concavity, a, b, samples)
+}
+
+/// Return an estimate of a root of `f` found with a damped version
+/// of Newton's method starting at `x0`, or `None` if no root is found.
+///
+/// Each iteration computes the Newton step `f(x) / f'(x)`, estimating
+/// `f'(x)` with `slope_at()`. If taking the full step would not decrease
+/// `|f(x)|`, the step is halved until it does. This prevents the
+/// oscillation and divergence plain Newton's method can suffer from when
+/// started far from a root, at the cost of possibly converging to a local
+/// minimum of `|f(x)|` that is not a root.
+///
+/// A root is found once `|f(x)| <= tol`. `None` is returned if this does
+/// not happen within `max_iter` iterations, or if no step decreasing
+/// `|f(x)|` can be found.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// // plain Newton's method diverges from x = 2 for atan(x)
+/// let f = func![|x: f64| x.atan()];
+/// let root = find_root_damped(&f, 2.0, 1.0e-12, 100).unwrap();
+/// assert!(root.abs() < 1.0e-12);
+/// # }
+/// ```
+pub fn find_root_damped(f: &Function, x0: f64, tol: f64, 
+                        max_iter: u32) -> Option<f64> {
+    let mut x = x0;
+    let mut fx = f(x);
+    for _ in 0..max_iter {
+        if fx.abs() <= tol {
+            return Some(x);
+        }
+
+        let slope = slope_at(f, x);
+        if slope == 0.0 || !slope.is_finite() {
+            return None;
+        }
+
+        let step = fx / slope;
+        let mut t = 1.0;
+        loop {
+            let x_next = x - t * step;
+            let fx_next = f(x_next);
+            if fx_next.abs() < fx.abs() {
+                x = x_next;
+                fx = fx_next;
+                break;
+            }
+
+            t /= 2.0;
+            if t < f64::EPSILON {
+                return None;
+            }
+        }
+    }
+
+    if fx.abs() <= tol { Some(x) } else { None }
+}
+
+/// Return an estimate of a root of `f` in `[a, b]` found with the
+/// bisection method, or `None` if `f(a)` and `f(b)` have the same sign.
+///
+/// The interval is repeatedly halved, keeping the half where the sign
+/// of `f` changes, until it is no wider than `2 * tol`, or until it can
+/// no longer be split due to floating point precision. The midpoint
+/// of the final interval is returned, which is within `tol` of a root.
+///
+/// Unlike `find_root_damped()`, no derivative is needed, and the method
+/// always converges if `f` is continuous and changes sign over `[a, b]`.
+/// However, convergence is slow, gaining a single bit of precision with
+/// each step.
+///
+/// If `f(a)` or `f(b)` is zero, that end is returned.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x * x - 2.0];
+/// let root = bisect(&f, 1.0, 2.0, 1.0e-12).unwrap();
+///
+/// assert!((root - 2.0f64.sqrt()).abs() <= 1.0e-12);
+/// assert_eq!(bisect(&f, 2.0, 3.0, 1.0e-12), None);
+/// # }
+/// ```
+pub fn bisect(f: &Function, a: f64, b: f64, tol: f64) -> Option<f64> {
+    let (fa, fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    } else if fb == 0.0 {
+        return Some(b);
+    } else if fa.is_nan() || fb.is_nan() || fa * fb > 0.0 {
+        return None;
+    }
+
+    Some(bisect_sign_change(&|x| f(x), a, b, tol))
+}
+
+/// Return an estimate of a root of `f` found with the secant method
+/// starting at `x0` and `x1`, or `None` if no root is found.
+///
+/// Each iteration replaces the older of the two points with the root
+/// of the line through both points on `f`,
+///
+/// ```text
+///                      x1 - x0
+/// x2 = x1 - f(x1) * -------------
+///                   f(x1) - f(x0)
+/// ```
+///
+/// which needs no derivative, and converges nearly as quickly as
+/// Newton's method when started close to a root.
+///
+/// A root is found once `|f(x)| <= tol`. `None` is returned if this does
+/// not happen within `max_iter` iterations, or if `f(x1)` and `f(x0)` are
+/// so close that the line through them is flat to within floating point
+/// precision.
+///
+/// Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::derivative::*;
+///
+/// let f = func![|x: f64| x * x - 2.0];
+/// let root = secant(&f, 1.0, 2.0, 1.0e-12, 100).unwrap();
+///
+/// assert!((root - 2.0f64.sqrt()).abs() < 1.0e-12);
+/// assert_eq!(secant(&f, -1.0, 1.0, 1.0e-12, 100), None);
+/// # }
+/// ```
+pub fn secant(f: &Function, x0: f64, x1: f64, tol: f64, 
+              max_iter: u32) -> Option<f64> {
+    let (mut x0, mut x1) = (x0, x1);
+    let (mut f0, mut f1) = (f(x0), f(x1));
+    for _ in 0..max_iter {
+        if f1.abs() <= tol {
+            return Some(x1);
+        }
+
+        let denom = f1 - f0;
+        if denom.abs() <= f64::EPSILON * f0.abs().max(f1.abs()) {
+            return None;
+        }
+
+        let x2 = x1 - f1 * (x1 - x0) / denom;
+        if !x2.is_finite() {
+            return None;
+        }
+
+        x0 = x1;
+        f0 = f1;
+        x1 = x2;
+        f1 = f(x1);
+    }
+
+    if f1.abs() <= tol { Some(x1) } else { None }
+}
+
+// helper function to find the points in [a, b] where `g` changes sign,
+// checking `samples` subintervals and refining each sign change with
+// bisection, zeros at the ends of subintervals count only if the ends
+// on either side of them have opposite signs
+fn sign_changes<F>(g: &F, a: f64, b: f64, samples: usize) -> Vec<f64>
+    where F: Fn(f64) -> f64 {
+    assert!(samples != 0, "cannot sample zero subintervals!");
+
+    let delta = (b - a) / samples as f64;
+    let points: Vec<(f64, f64)> = (0..(samples + 1)).map(|i| {
+        let x = if i == samples { b } else { a + i as f64 * delta };
+        (x, g(x))
+    }).collect();
+
+    let mut roots: Vec<f64> = Vec::new();
+    for i in 0..points.len() {
+        let (x, y) = points[i];
+        if y == 0.0 {
+            if i > 0 && i + 1 < points.len() && 
+               points[i - 1].1 * points[i + 1].1 < 0.0 {
+                roots.push(x);
+            }
+        } else if i + 1 < points.len() && y * points[i + 1].1 < 0.0 {
+            roots.push(bisect_sign_change(g, x, points[i + 1].0, 0.0));
+        }
+    }
+
+    roots
+}
+
+// helper function to find the point in [lo, hi] where `g` changes sign
+// to within `tol`, `g(lo)` and `g(hi)` must have opposite signs
+fn bisect_sign_change<F>(g: &F, mut lo: f64, mut hi: f64, tol: f64) -> f64
+    where F: Fn(f64) -> f64 {
+    let lo_sign = g(lo).signum();
+    for _ in 0..2_100 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid == lo || mid == hi || (hi - lo).abs() / 2.0 <= tol {
+            break;
+        }
+
+        let y = g(mid);
+        if y == 0.0 {
+            return mid;
+        } else if y.signum() == lo_sign {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    lo + (hi - lo) / 2.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,4 +848,305 @@ mod tests {
         assert_eq!(f_s_deriv(40.4), concavity_at(&f, 40.4));
         assert_eq!(f_s_deriv(12.3), concavity_at(&f, 12.3));
     }
+
+#[test]
+    fn t_taylor() {
+        let f = func!(|x: f64| x * x);
+
+        let coefficients = taylor_coefficients(&f, 0.0, 2);
+        assert_eq!(coefficients.len(), 3);
+        assert_fp!(coefficients[0], 0.0, 0.001);
+        assert_fp!(coefficients[1], 0.0, 0.001);
+        assert_fp!(coefficients[2], 1.0, 0.001);
+
+        let coefficients = taylor_coefficients(&f, 3.0, 2);
+        assert_fp!(coefficients[0], 9.0, 0.001);
+        assert_fp!(coefficients[1], 6.0, 0.001);
+        assert_fp!(coefficients[2], 1.0, 0.1);
+
+        assert_eq!(taylor_coefficients(&f, 3.0, 0), vec![9.0]);
+
+        let poly = taylor_poly(&f, 3.0, 2);
+        for x in [-2.0, 0.0, 1.0, 3.0, 4.5] {
+            assert_fp!(poly(x), f(x), 0.1);
+        }
+
+        let g = func!(|x: f64| x.sin());
+        let poly = taylor_poly(&g, 0.0, 1);
+        assert_fp!(poly(0.01), 0.01, 0.0001);
+
+        let poly = taylor_poly(&g, 0.0, 0);
+        assert_eq!(poly(1.0), 0.0);
+    }
+
+#[test]
+    fn t_slope_at_auto() {
+        assert_fp!(optimal_h(0.0), 6.055454e-6, 1.0e-11);
+        assert_eq!(optimal_h(-1.0e6), optimal_h(1.0e6));
+        assert_fp!(optimal_h(1.0e6) / optimal_h(1.0), 1.0e6, 1.0e-3);
+
+        // relative errors of the estimates at x = 1e6
+        let x: f64 = 1.0e6;
+        let tests: Vec<(Function, f64)> = vec![
+            (func!(|x: f64| x.powi(3)), 3.0 * x * x),
+            (func!(|x: f64| x.sqrt()),  0.5 / x.sqrt()),
+            (func!(|x: f64| x.ln()),    1.0 / x),
+        ];
+
+        for (f, slope) in tests {
+            let fixed_error = ((slope_at(&f, x) - slope) / slope).abs();
+            let auto_error = ((slope_at_auto(&f, x) - slope) / slope).abs();
+            assert!(fixed_error > 1.0e-6);
+            assert!(auto_error < 1.0e-9);
+        }
+
+        let f = func!(|x: f64| x * x);
+        assert_fp!(slope_at_auto(&f, x), 2.0 * x, 1.0e-3);
+
+        let f = func!(|x: f64| x.sin());
+        for x in [-2.0, 0.0, 0.5, 3.0] {
+            assert_fp!(slope_at_auto(&f, x), x.cos(), 1.0e-9);
+        }
+    }
+
+#[test]
+    fn t_gradient_n() {
+        let f: FunctionN = Rc::new(|x: &[f64]| x[0] * x[0] + 2.0 * x[1] * x[1]);
+        let gradient = gradient_n(&f, &[1.0, 2.0]);
+        assert_eq!(gradient.len(), 2);
+        assert_fp!(gradient[0], 2.0, 1.0e-6);
+        assert_fp!(gradient[1], 8.0, 1.0e-6);
+
+        let gradient = gradient_n(&f, &[0.0, 0.0]);
+        assert_fp!(gradient[0], 0.0, 1.0e-9);
+        assert_fp!(gradient[1], 0.0, 1.0e-9);
+
+        let f: FunctionN = Rc::new(|x: &[f64]| x[0].sin() * x[1].exp() + x[2]);
+        let gradient = gradient_n(&f, &[0.5, -1.0, 3.0]);
+        assert_fp!(gradient[0], 0.5f64.cos() * (-1.0f64).exp(), 1.0e-9);
+        assert_fp!(gradient[1], 0.5f64.sin() * (-1.0f64).exp(), 1.0e-9);
+        assert_fp!(gradient[2], 1.0, 1.0e-9);
+
+        // a single variable matches slope_at_auto()
+        let g = func!(|x: f64| x.ln());
+        let f: FunctionN = Rc::new(|x: &[f64]| x[0].ln());
+        assert_eq!(gradient_n(&f, &[1.0e6]), vec![slope_at_auto(&g, 1.0e6)]);
+
+        let f: FunctionN = Rc::new(|_: &[f64]| 1.0);
+        assert_eq!(gradient_n(&f, &[]), vec![]);
+    }
+
+#[test]
+    fn t_hessian() {
+        let f: FunctionN = Rc::new(|x: &[f64]| x[0] * x[0] + x[1] * x[1]);
+        for point in [[0.0, 0.0], [1.0, -2.0], [100.0, 0.5]] {
+            let hess = hessian(&f, &point);
+            assert_eq!(hess.len(), 2);
+            assert_fp!(hess[0][0], 2.0, 1.0e-4);
+            assert_fp!(hess[0][1], 0.0, 1.0e-4);
+            assert_fp!(hess[1][0], 0.0, 1.0e-4);
+            assert_fp!(hess[1][1], 2.0, 1.0e-4);
+        }
+
+        let f: FunctionN = Rc::new(|x: &[f64]| {
+            x[0] * x[0] * x[1] + x[1].sin() * x[2] + x[2].exp()
+        });
+        let hess = hessian(&f, &[1.0, 2.0, 0.5]);
+        let expected = [[4.0,            2.0,             0.0],
+                        [2.0, -(2.0f64.sin()) * 0.5, 2.0f64.cos()],
+                        [0.0,   2.0f64.cos(),     0.5f64.exp()]];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_fp!(hess[i][j], expected[i][j], 1.0e-4);
+                assert_eq!(hess[i][j], hess[j][i]);
+            }
+        }
+
+        let f: FunctionN = Rc::new(|_: &[f64]| 1.0);
+        assert_eq!(hessian(&f, &[]), Vec::<Vec<f64>>::new());
+    }
+
+#[test]
+    fn t_find_extrema() {
+        let f = func!(|x: f64| x * x);
+        let extrema = find_extrema(&f, -1.0, 1.0, 10);
+        assert_eq!(extrema.len(), 1);
+        assert_fp!(extrema[0].0, 0.0, 1.0e-9);
+        assert_eq!(extrema[0].1, ExtremumKind::Minimum);
+
+        let extrema = find_extrema(&f, -1.0, 2.0, 7);
+        assert_eq!(extrema.len(), 1);
+        assert_fp!(extrema[0].0, 0.0, 1.0e-9);
+        assert_eq!(extrema[0].1, ExtremumKind::Minimum);
+
+        assert_eq!(find_extrema(&f, 1.0, 2.0, 10), vec![]);
+
+        let f = func!(|x: f64| -x * x);
+        let extrema = find_extrema(&f, -1.0, 2.0, 7);
+        assert_eq!(extrema.len(), 1);
+        assert_fp!(extrema[0].0, 0.0, 1.0e-9);
+        assert_eq!(extrema[0].1, ExtremumKind::Maximum);
+
+        let f = func!(|x: f64| x.sin());
+        let extrema = find_extrema(&f, 0.0, 2.0 * ::std::f64::consts::PI, 100);
+        assert_eq!(extrema.len(), 2);
+        assert_fp!(extrema[0].0, ::std::f64::consts::PI / 2.0, 1.0e-6);
+        assert_eq!(extrema[0].1, ExtremumKind::Maximum);
+        assert_fp!(extrema[1].0, 3.0 * ::std::f64::consts::PI / 2.0, 1.0e-6);
+        assert_eq!(extrema[1].1, ExtremumKind::Minimum);
+
+        let f = func!(|x: f64| x * x * x - 3.0 * x);
+        let extrema = find_extrema(&f, 3.0, -3.0, 13);
+        assert_eq!(extrema.len(), 2);
+        assert_fp!(extrema[0].0, 1.0, 1.0e-6);
+        assert_eq!(extrema[0].1, ExtremumKind::Minimum);
+        assert_fp!(extrema[1].0, -1.0, 1.0e-6);
+        assert_eq!(extrema[1].1, ExtremumKind::Maximum);
+
+        // no extremum where the slope is zero but does not change sign
+        let f = func!(|x: f64| x * x * x);
+        assert_eq!(find_extrema(&f, -1.0, 1.0, 10), vec![]);
+    }
+
+#[test]
+#[should_panic]
+    fn t_find_extrema_panic() {
+        let f = func!(|x: f64| x * x);
+        find_extrema(&f, -1.0, 1.0, 0);
+    }
+
+#[test]
+    fn t_find_inflections() {
+        let f = func!(|x: f64| x * x * x);
+        let inflections = find_inflections(&f, -1.0, 1.0, 10);
+        assert_eq!(inflections.len(), 1);
+        assert_fp!(inflections[0], 0.0, 1.0e-3);
+
+        let inflections = find_inflections(&f, -1.0, 2.0, 7);
+        assert_eq!(inflections.len(), 1);
+        assert_fp!(inflections[0], 0.0, 1.0e-3);
+
+        let f = func!(|x: f64| x * x);
+        assert_eq!(find_inflections(&f, -1.0, 1.0, 10), vec![]);
+        assert_eq!(find_inflections(&f, -5.0, 5.0, 100), vec![]);
+
+        let f = func!(|x: f64| x.sin());
+        let inflections = find_inflections(&f, 1.0, 7.0, 60);
+        assert_eq!(inflections.len(), 2);
+        assert_fp!(inflections[0], ::std::f64::consts::PI, 1.0e-3);
+        assert_fp!(inflections[1], 2.0 * ::std::f64::consts::PI, 1.0e-3);
+
+        let f = func!(|x: f64| x * x * x - 3.0 * x * x);
+        let inflections = find_inflections(&f, 5.0, -5.0, 10);
+        assert_eq!(inflections.len(), 1);
+        assert_fp!(inflections[0], 1.0, 1.0e-3);
+    }
+
+#[test]
+#[should_panic]
+    fn t_find_inflections_panic() {
+        let f = func!(|x: f64| x * x * x);
+        find_inflections(&f, -1.0, 1.0, 0);
+    }
+
+#[test]
+    fn t_find_root_damped() {
+        // plain Newton's method oscillates with growing magnitude
+        let f = func!(|x: f64| x.atan());
+        let mut x: f64 = 2.0;
+        for _ in 0..4 {
+            let x_next = x - f(x) / slope_at(&f, x);
+            assert!(x_next.abs() > x.abs());
+            x = x_next;
+        }
+
+        let root = find_root_damped(&f, 2.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, 0.0, 1.0e-12);
+
+        // plain Newton's method oscillates between -2^n and 2^(n+1)
+        let f = func!(|x: f64| x.cbrt());
+        let root = find_root_damped(&f, 1.0, 1.0e-9, 200).unwrap();
+        assert_fp!(root, 0.0, 1.0e-9);
+
+        let f = func!(|x: f64| x * x - 2.0);
+        let root = find_root_damped(&f, 1.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, 2.0f64.sqrt(), 1.0e-9);
+        let root = find_root_damped(&f, -10.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, -(2.0f64.sqrt()), 1.0e-9);
+
+        assert_eq!(find_root_damped(&f, 2.0f64.sqrt(), 1.0e-12, 0), 
+                   Some(2.0f64.sqrt()));
+        assert_eq!(find_root_damped(&f, 1.0, 1.0e-12, 0), None);
+        assert_eq!(find_root_damped(&f, 1.0, 1.0e-12, 1), None);
+
+        // no root
+        let f = func!(|x: f64| x * x + 1.0);
+        assert_eq!(find_root_damped(&f, 3.0, 1.0e-12, 100), None);
+        assert_eq!(find_root_damped(&f, 0.0, 1.0e-12, 100), None);
+    }
+
+#[test]
+    fn t_bisect() {
+        let f = func!(|x: f64| x * x - 2.0);
+        let root = bisect(&f, 1.0, 2.0, 1.0e-12).unwrap();
+        assert_fp!(root, 2.0f64.sqrt(), 1.0e-12);
+        let root = bisect(&f, 2.0, 1.0, 1.0e-12).unwrap();
+        assert_fp!(root, 2.0f64.sqrt(), 1.0e-12);
+        let root = bisect(&f, -2.0, 0.0, 0.0).unwrap();
+        assert_fp!(root, -(2.0f64.sqrt()), 1.0e-15);
+
+        let root = bisect(&f, 1.0, 2.0, 0.25).unwrap();
+        assert_fp!(root, 2.0f64.sqrt(), 0.25);
+
+        assert_eq!(bisect(&f, 2.0, 3.0, 1.0e-12), None);
+        assert_eq!(bisect(&f, -2.0, 2.0, 1.0e-12), None);
+
+        let f = func!(|x: f64| x * x - 4.0);
+        assert_eq!(bisect(&f, 2.0, 3.0, 1.0e-12), Some(2.0));
+        assert_eq!(bisect(&f, 0.0, -2.0, 1.0e-12), Some(-2.0));
+
+        let f = func!(|x: f64| x.cos());
+        let root = bisect(&f, 0.0, 3.0, 1.0e-9).unwrap();
+        assert_fp!(root, ::std::f64::consts::PI / 2.0, 1.0e-9);
+
+        let f = func!(|x: f64| if x < 0.5 { -1.0 } else { f64::NAN });
+        assert_eq!(bisect(&f, 0.0, 1.0, 1.0e-9), None);
+    }
+
+#[test]
+    fn t_secant() {
+        let f = func!(|x: f64| x * x - 2.0);
+        let root = secant(&f, 1.0, 2.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, bisect(&f, 1.0, 2.0, 1.0e-12).unwrap(), 1.0e-11);
+        assert_fp!(root, find_root_damped(&f, 1.0, 1.0e-12, 100).unwrap(), 
+                   1.0e-11);
+
+        let root = secant(&f, -3.0, -2.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, -(2.0f64.sqrt()), 1.0e-11);
+
+        let f = func!(|x: f64| x.cos() - x);
+        let root = secant(&f, 0.0, 1.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, bisect(&f, 0.0, 1.0, 1.0e-12).unwrap(), 1.0e-11);
+        assert_fp!(root, find_root_damped(&f, 0.0, 1.0e-12, 100).unwrap(), 
+                   1.0e-11);
+
+        let f = func!(|x: f64| x * x * x - x - 1.0);
+        let root = secant(&f, 1.0, 2.0, 1.0e-12, 100).unwrap();
+        assert_fp!(root, 1.324717957244746, 1.0e-11);
+
+        // flat secant lines
+        let f = func!(|x: f64| x * x - 2.0);
+        assert_eq!(secant(&f, -1.0, 1.0, 1.0e-12, 100), None);
+        assert_eq!(secant(&f, 1.0, 1.0, 1.0e-12, 100), None);
+        let f = func!(|_x: f64| 1.0);
+        assert_eq!(secant(&f, 0.0, 1.0, 1.0e-12, 100), None);
+
+        // no root, or not enough iterations
+        let f = func!(|x: f64| x * x + 1.0);
+        assert_eq!(secant(&f, 1.0, 2.0, 1.0e-12, 100), None);
+        let f = func!(|x: f64| x * x - 2.0);
+        assert_eq!(secant(&f, 1.0, 2.0, 1.0e-12, 2), None);
+        assert_eq!(secant(&f, 1.0, 2.0f64.sqrt(), 1.0e-12, 0), 
+                   Some(2.0f64.sqrt()));
+    }
 }
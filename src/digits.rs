@@ -0,0 +1,586 @@
+//! Module for working with the digits of integers.
+//!
+//! This module has functions for converting integers to and
+//! from arbitrary bases, computing digit sums and digital roots,
+//! testing digit-based properties such as palindromes, and finding
+//! the period of decimal expansions.
+
+use super::factor;
+use super::prime;
+use super::totient;
+
+/// Return a `Vec<u64>` of the digits of `n` when written in `base`,
+/// starting with the most significant digit.
+///
+/// Zero is represented as a single zero digit.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::to_base;
+/// assert_eq!(to_base(255, 16), vec![15, 15]);
+/// assert_eq!(to_base(6, 2), vec![1, 1, 0]);
+/// ```
+pub fn to_base(mut n: u64, base: u64) -> Vec<u64> {
+    assert!(base >= 2, "base must be at least two!");
+
+    if n == 0 {
+        return vec![0];
+    }
+
+    let mut digits: Vec<u64> = Vec::new();
+    while n != 0 {
+        digits.push(n % base);
+        n /= base;
+    }
+
+    digits.reverse();
+    digits
+}
+
+/// Return the value of `digits` when read in `base`, starting
+/// with the most significant digit.
+///
+/// This is the inverse of `to_base()`. An empty slice has
+/// a value of zero.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, if any of the digits are not
+/// less than `base`, or if the value is larger than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::from_base;
+/// assert_eq!(from_base(&[15, 15], 16), 255);
+/// assert_eq!(from_base(&[1, 1, 0], 2), 6);
+/// ```
+pub fn from_base(digits: &[u64], base: u64) -> u64 {
+    assert!(base >= 2, "base must be at least two!");
+
+    let mut n: u64 = 0;
+    for digit in digits {
+        assert!(*digit < base, "{} is not a valid base {} digit!", 
+                digit, base);
+        n = n.checked_mul(base)
+             .and_then(|n| n.checked_add(*digit))
+             .expect("value is larger than u64::MAX!");
+    }
+
+    n
+}
+
+/// Return `true` if `n` is a palindrome when written in `base`,
+/// that is, its digits read the same forwards and backwards.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::is_palindrome;
+/// assert_eq!(is_palindrome(121, 10), true);
+/// assert_eq!(is_palindrome(10, 2), false);
+/// ```
+pub fn is_palindrome(n: u64, base: u64) -> bool {
+    let digits = to_base(n, base);
+    digits.iter().eq(digits.iter().rev())
+}
+
+/// Return the `n` digit repunit in `base`, that is, the number
+/// whose representation in `base` is `n` ones.
+///
+/// ```text
+///         base^n - 1
+/// R(n) = ------------
+///         base - 1
+/// ```
+///
+/// The zero digit repunit is zero.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, or if the repunit is larger
+/// than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::repunit;
+/// assert_eq!(repunit(3, 10), 111);
+/// assert_eq!(repunit(4, 2), 15);
+/// ```
+pub fn repunit(n: u64, base: u64) -> u64 {
+    assert!(base >= 2, "base must be at least two!");
+
+    let mut r: u64 = 0;
+    for _ in 0..n {
+        r = r.checked_mul(base)
+             .and_then(|r| r.checked_add(1))
+             .expect("repunit is larger than u64::MAX!");
+    }
+
+    r
+}
+
+/// Return `true` if the `n` digit base ten repunit, `R(n)`, is prime.
+///
+/// Because `R(a)` divides `R(a * b)`, `R(n)` can only be prime if
+/// `n` is prime, so `false` is returned for composite `n` without
+/// computing the repunit. Otherwise the repunit is tested with
+/// `prime::is_prime()`.
+///
+/// # Panics
+///
+/// Panics if `n` is prime and `R(n)` is larger than `u64::MAX`,
+/// that is, if `n` is a prime greater than `19`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::is_repunit_prime;
+/// assert_eq!(is_repunit_prime(2), true);
+/// assert_eq!(is_repunit_prime(3), false);
+/// ```
+pub fn is_repunit_prime(n: u64) -> bool {
+    if !prime::is_prime(n) {
+        return false;
+    }
+
+    prime::is_prime(repunit(n, 10))
+}
+
+/// Return the sum of the digits of `n` when written in `base`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::digit_sum;
+/// assert_eq!(digit_sum(12345, 10), 15);
+/// assert_eq!(digit_sum(255, 16), 30);
+/// ```
+pub fn digit_sum(mut n: u64, base: u64) -> u64 {
+    assert!(base >= 2, "base must be at least two!");
+
+    let mut sum = 0;
+    while n != 0 {
+        sum += n % base;
+        n /= base;
+    }
+
+    sum
+}
+
+/// Return the digital root of `n` when written in `base`, that is,
+/// the single digit reached by repeatedly taking the digit sum of `n`.
+///
+/// This function uses the congruence formula
+///
+/// ```text
+/// dr(n) = 1 + ((n - 1) mod (base - 1))
+/// ```
+///
+/// rather than repeatedly calling `digit_sum()`.
+///
+/// The digital root of zero is zero.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::digital_root;
+/// assert_eq!(digital_root(12345, 10), 6);
+/// assert_eq!(digital_root(255, 16), 15);
+/// ```
+pub fn digital_root(n: u64, base: u64) -> u64 {
+    assert!(base >= 2, "base must be at least two!");
+
+    if n == 0 {
+        return 0;
+    }
+
+    1 + (n - 1) % (base - 1)
+}
+
+/// Return `true` if `n` is an automorphic number, that is, if the
+/// base ten representation of `n^2` ends with the digits of `n`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::is_automorphic;
+/// assert_eq!(is_automorphic(25), true);  // 25^2 = 625
+/// assert_eq!(is_automorphic(26), false); // 26^2 = 676
+/// ```
+pub fn is_automorphic(n: u64) -> bool {
+    let mut modulus: u128 = 10;
+    while modulus <= n as u128 {
+        modulus *= 10;
+    }
+
+    (n as u128 * n as u128) % modulus == n as u128
+}
+
+/// Return `true` if `n` is a narcissistic number, that is, if `n` is
+/// equal to the sum of its base ten digits, each raised to the power
+/// of the number of digits in `n`.
+///
+/// Zero is considered narcissistic, as `0 = 0^1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::is_narcissistic;
+/// assert_eq!(is_narcissistic(153), true); // 1^3 + 5^3 + 3^3 = 153
+/// assert_eq!(is_narcissistic(154), false);
+/// ```
+pub fn is_narcissistic(n: u64) -> bool {
+    let digits = to_base(n, 10);
+    let k = digits.len() as u32;
+
+    let sum: u128 = digits.iter().map(|&d| (d as u128).pow(k)).sum();
+    sum == n as u128
+}
+
+/// Return `true` if `n` is a happy number, that is, if repeatedly
+/// replacing `n` with the sum of the squares of its base ten digits
+/// eventually reaches `1`.
+///
+/// Every unhappy number eventually enters the cycle
+/// `4, 16, 37, 58, 89, 145, 42, 20, 4`, which is detected with
+/// Floyd's cycle-finding algorithm.
+///
+/// Zero is not considered happy, as it never leaves zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::is_happy;
+/// assert_eq!(is_happy(7), true);  // 7, 49, 97, 130, 10, 1
+/// assert_eq!(is_happy(4), false);
+/// ```
+pub fn is_happy(n: u64) -> bool {
+    let mut slow = n;
+    let mut fast = n;
+    loop {
+        slow = digit_square_sum(slow);
+        fast = digit_square_sum(digit_square_sum(fast));
+        if slow == fast {
+            return slow == 1;
+        }
+    }
+}
+
+// helper function to sum the squares of the base ten digits of `n`
+fn digit_square_sum(mut n: u64) -> u64 {
+    let mut sum = 0;
+    while n != 0 {
+        let d = n % 10;
+        sum += d * d;
+        n /= 10;
+    }
+
+    sum
+}
+
+/// Return the lengths of the non-repeating and repeating parts of
+/// the base ten expansion of `num / den`, as `(pre_period, period)`.
+///
+/// After reducing the fraction, the length of the non-repeating
+/// part is the larger of the powers of `2` and `5` in `den`. The
+/// length of the repeating part is the multiplicative order of `10`
+/// modulo what remains of `den`, see `totient::multiplicative_order()`.
+///
+/// Terminating expansions have a period of `0`.
+///
+/// # Panics
+///
+/// Panics if `den` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::digits::decimal_period;
+/// assert_eq!(decimal_period(1, 6), (1, 1)); // 0.1(6)
+/// assert_eq!(decimal_period(1, 7), (0, 6)); // 0.(142857)
+/// assert_eq!(decimal_period(1, 2), (1, 0)); // 0.5
+/// ```
+pub fn decimal_period(num: u64, den: u64) -> (usize, usize) {
+    assert!(den != 0, "denominator cannot be zero!");
+
+    let mut den = den / factor::gcd(num, den);
+
+    let mut twos = 0;
+    while den & 0x01 == 0 {
+        den >>= 1;
+        twos += 1;
+    }
+
+    let mut fives = 0;
+    while den % 5 == 0 {
+        den /= 5;
+        fives += 1;
+    }
+
+    let pre_period = if twos > fives { twos } else { fives };
+    if den == 1 {
+        return (pre_period, 0);
+    }
+
+    let period = totient::multiplicative_order(10, den).unwrap();
+    (pre_period, period as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_to_base() {
+        assert_eq!(to_base(0, 10), vec![0]);
+        assert_eq!(to_base(7, 10), vec![7]);
+        assert_eq!(to_base(1234, 10), vec![1, 2, 3, 4]);
+        assert_eq!(to_base(255, 16), vec![15, 15]);
+        assert_eq!(to_base(256, 16), vec![1, 0, 0]);
+        assert_eq!(to_base(10, 2), vec![1, 0, 1, 0]);
+        assert_eq!(to_base(u64::MAX, 2), vec![1; 64]);
+        assert_eq!(to_base(u64::MAX, u64::MAX), vec![1, 0]);
+    }
+
+#[test]
+    fn t_from_base() {
+        assert_eq!(from_base(&[], 10), 0);
+        assert_eq!(from_base(&[0], 10), 0);
+        assert_eq!(from_base(&[0, 0, 7], 10), 7);
+        assert_eq!(from_base(&[1, 2, 3, 4], 10), 1234);
+        assert_eq!(from_base(&[15, 15], 16), 255);
+        assert_eq!(from_base(&[1; 64], 2), u64::MAX);
+
+        for base in 2..40 {
+            for n in 0..1_000 {
+                assert_eq!(from_base(&to_base(n, base), base), n);
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_from_base_panic() {
+        from_base(&[1, 2], 2);
+    }
+
+#[test]
+#[should_panic]
+    fn t_from_base_panic_2() {
+        from_base(&[1; 65], 2);
+    }
+
+#[test]
+    fn t_is_palindrome() {
+        assert!(is_palindrome(0, 10));
+        assert!(is_palindrome(7, 10));
+        assert!(is_palindrome(121, 10));
+        assert!(is_palindrome(12321, 10));
+        assert!(is_palindrome(9, 2));
+        assert!(is_palindrome(255, 16));
+        assert!(is_palindrome(u64::MAX, 2));
+
+        assert!(!is_palindrome(10, 10));
+        assert!(!is_palindrome(123, 10));
+        assert!(!is_palindrome(10, 2));
+        assert!(!is_palindrome(256, 16));
+    }
+
+#[test]
+    fn t_repunit() {
+        assert_eq!(repunit(0, 10), 0);
+        assert_eq!(repunit(1, 10), 1);
+        assert_eq!(repunit(3, 10), 111);
+        assert_eq!(repunit(19, 10), 1_111_111_111_111_111_111);
+        assert_eq!(repunit(20, 10), 11_111_111_111_111_111_111);
+        assert_eq!(repunit(64, 2), u64::MAX);
+        assert_eq!(to_base(repunit(7, 13), 13), vec![1; 7]);
+    }
+
+#[test]
+#[should_panic]
+    fn t_repunit_panic() {
+        repunit(21, 10);
+    }
+
+#[test]
+    fn t_is_repunit_prime() {
+        assert!(is_repunit_prime(2));
+        assert!(is_repunit_prime(19));
+
+        assert!(!is_repunit_prime(0));
+        assert!(!is_repunit_prime(1));
+        assert!(!is_repunit_prime(3));
+        assert!(!is_repunit_prime(4));
+        assert!(!is_repunit_prime(5));
+        assert!(!is_repunit_prime(18));
+        assert!(!is_repunit_prime(1_000));
+    }
+
+#[test]
+#[should_panic]
+    fn t_is_repunit_prime_panic() {
+        is_repunit_prime(23);
+    }
+
+#[test]
+    fn t_digit_sum() {
+        assert_eq!(digit_sum(0, 10), 0);
+        assert_eq!(digit_sum(7, 10), 7);
+        assert_eq!(digit_sum(12345, 10), 15);
+        assert_eq!(digit_sum(255, 16), 30);
+        assert_eq!(digit_sum(255, 2), 8);
+        assert_eq!(digit_sum(u64::MAX, 2), 64);
+        assert_eq!(digit_sum(u64::MAX, u64::MAX), 1);
+    }
+
+#[test]
+    fn t_digital_root() {
+        assert_eq!(digital_root(0, 10), 0);
+        assert_eq!(digital_root(9, 10), 9);
+        assert_eq!(digital_root(18, 10), 9);
+        assert_eq!(digital_root(12345, 10), 6);
+        assert_eq!(digital_root(255, 16), 15);
+        assert_eq!(digital_root(256, 16), 1);
+        assert_eq!(digital_root(6, 2), 1);
+
+        for base in 2..20 {
+            for n in 0..2_000 {
+                let mut dr = n;
+                while dr >= base {
+                    dr = digit_sum(dr, base);
+                }
+                assert_eq!(digital_root(n, base), dr);
+            }
+        }
+    }
+
+#[test]
+    fn t_is_automorphic() {
+        for n in [0, 1, 5, 6, 25, 76, 376, 625, 9376, 
+                  7_109_376, 1_787_109_376] {
+            assert!(is_automorphic(n));
+        }
+
+        for n in [2, 10, 24, 26, 75, 100, 9375, u64::MAX] {
+            assert!(!is_automorphic(n));
+        }
+
+        let automorphic: Vec<u64> = (0..10_000).filter(|&n| is_automorphic(n))
+                                               .collect();
+        assert_eq!(automorphic, vec![0, 1, 5, 6, 25, 76, 376, 625, 9376]);
+    }
+
+#[test]
+    fn t_is_narcissistic() {
+        for n in [0, 1, 9, 153, 370, 371, 407, 1634, 8208, 9474, 
+                  4_679_307_774, 4_929_273_885_928_088_826] {
+            assert!(is_narcissistic(n));
+        }
+
+        for n in [10, 100, 152, 154, 9475, u64::MAX] {
+            assert!(!is_narcissistic(n));
+        }
+
+        let narcissistic: Vec<u64> = (0..10_000).filter(|&n| is_narcissistic(n))
+                                                .collect();
+        assert_eq!(narcissistic, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9,
+                                      153, 370, 371, 407, 1634, 8208, 9474]);
+    }
+
+#[test]
+    fn t_is_happy() {
+        assert!(is_happy(1));
+        assert!(is_happy(7));
+        assert!(is_happy(10));
+        assert!(is_happy(19));
+        assert!(is_happy(23));
+        assert!(is_happy(100));
+
+        assert!(!is_happy(0));
+        assert!(!is_happy(2));
+        assert!(!is_happy(4));
+        assert!(!is_happy(16));
+        assert!(!is_happy(89));
+        assert!(!is_happy(u64::MAX));
+
+        let happy: Vec<u64> = (1..50).filter(|&n| is_happy(n)).collect();
+        assert_eq!(happy, vec![1, 7, 10, 13, 19, 23, 28, 31, 32, 44, 49]);
+        assert_eq!((1..1_001).filter(|&n| is_happy(n)).count(), 143);
+    }
+
+#[test]
+    fn t_decimal_period() {
+        assert_eq!(decimal_period(0, 7), (0, 0));
+        assert_eq!(decimal_period(7, 7), (0, 0));
+        assert_eq!(decimal_period(1, 1), (0, 0));
+        assert_eq!(decimal_period(1, 2), (1, 0));
+        assert_eq!(decimal_period(1, 3), (0, 1));
+        assert_eq!(decimal_period(1, 6), (1, 1));
+        assert_eq!(decimal_period(1, 7), (0, 6));
+        assert_eq!(decimal_period(22, 7), (0, 6));
+        assert_eq!(decimal_period(3, 12), (2, 0));
+        assert_eq!(decimal_period(1, 12), (2, 1));
+        assert_eq!(decimal_period(1, 81), (0, 9));
+        assert_eq!(decimal_period(1, 97), (0, 96));
+        assert_eq!(decimal_period(1, 1_000_000_007), (0, 1_000_000_006));
+        assert_eq!(decimal_period(1, 1 << 63), (63, 0));
+
+        // check against long division
+        for den in 1..200 {
+            for num in [1, 7, 150] {
+                let mut seen: Vec<Option<usize>> = vec![None; den as usize];
+                let mut rem = num % den;
+                let mut pos = 0;
+                while rem != 0 && seen[rem as usize].is_none() {
+                    seen[rem as usize] = Some(pos);
+                    rem = rem * 10 % den;
+                    pos += 1;
+                }
+
+                let expected = match seen.get(rem as usize) {
+                    Some(&Some(start)) if rem != 0 => (start, pos - start),
+                    _ => (pos, 0),
+                };
+                assert_eq!(decimal_period(num, den), expected);
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_decimal_period_panic() {
+        decimal_period(1, 0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_digit_sum_panic() {
+        digit_sum(10, 1);
+    }
+
+#[test]
+#[should_panic]
+    fn t_digital_root_panic() {
+        digital_root(10, 0);
+    }
+}
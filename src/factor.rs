@@ -1,13 +1,21 @@
 //! Module for working with integer factorization.
 //!
 //! This module contains functions for factoring integers, 
-//! computing the LCM and GCD of integers, and testing if
-//! integers are perfect squares and perfect cubes.
+//! computing the LCM and GCD of integers, testing if integers
+//! are perfect squares and perfect cubes, writing integers as
+//! sums of two squares, and computing the Möbius and Liouville
+//! functions.
 
 use std::cmp::min;
+use std::collections::HashMap;
 use std::mem;
+use super::digits;
+use super::modular;
 use super::prime;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Find the GCD of `a` and `b` using the Euclidean algorithm.
 ///
 /// This function will return `0` if both arguments are zero.
@@ -33,6 +41,46 @@ pub fn gcd(mut a: u64, mut b: u64) -> u64 {
     a
 }
 
+/// Return a tuple `(g, x, y)`, where `g` is the GCD of `a` and `b`,
+/// and `x` and `y` are integers such that
+///
+/// ```text
+/// a * x + b * y = g
+/// ```
+///
+/// This function uses the extended Euclidean algorithm. The
+/// coefficients are returned as `i128`s, as they can be larger
+/// than `i64::MAX` for large `a` and `b`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::gcd_ext;
+/// assert_eq!(gcd_ext(240, 46), (2, -9, 47));
+/// ```
+pub fn gcd_ext(a: u64, b: u64) -> (u64, i128, i128) {
+    let (mut r0, mut r1) = (a as i128, b as i128);
+    let (mut x0, mut x1) = (1i128, 0i128);
+    let (mut y0, mut y1) = (0i128, 1i128);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        let r2 = r0 - q * r1;
+        r0 = r1;
+        r1 = r2;
+
+        let x2 = x0 - q * x1;
+        x0 = x1;
+        x1 = x2;
+
+        let y2 = y0 - q * y1;
+        y0 = y1;
+        y1 = y2;
+    }
+
+    (r0 as u64, x0, y0)
+}
+
 /// Return the GCD of the set of integers
 ///
 /// This function works by applying the fact that the
@@ -124,6 +172,107 @@ pub fn lcm_all(set: &[u64]) -> u64 {
     lcm_
 }
 
+/// Return the LCM of `a` and `b`, or `None` if the LCM is
+/// larger than `u64::MAX`.
+///
+/// This function works in the same way as `lcm()`, but divides
+/// by the GCD before multiplying, and checks the multiplication
+/// for overflow.
+///
+/// If both `a` and `b` are zero, `Some(0)` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::checked_lcm;
+/// assert_eq!(checked_lcm(4, 6), Some(12));
+/// assert_eq!(checked_lcm(1 << 32, 3 << 32), Some(3 << 32));
+/// assert_eq!(checked_lcm(1 << 32, (1 << 32) + 1), None);
+/// ```
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 && b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Return the LCM of the set of integers, or `None` if the LCM
+/// is larger than `u64::MAX`.
+///
+/// This function works in the same way as `lcm_all()`, using
+/// `checked_lcm()` to compute each running LCM.
+///
+/// If an empty set is given, `Some(1)` will be returned.
+///
+/// # Examples
+/// 
+/// ```
+/// use reikna::factor::checked_lcm_all;
+/// let set: Vec<u64> = (1..21).collect();
+/// assert_eq!(checked_lcm_all(&set), Some(232_792_560));
+///
+/// let set: Vec<u64> = (1..51).collect();
+/// assert_eq!(checked_lcm_all(&set), None);
+/// ```
+pub fn checked_lcm_all(set: &[u64]) -> Option<u64> {
+    let mut lcm_ = 1;
+    for n in set {
+        lcm_ = checked_lcm(*n, lcm_)?;
+    }
+
+    Some(lcm_)
+}
+
+/// Return the Farey sequence of order `n`, that is, every reduced
+/// fraction in `[0, 1]` with a denominator no greater than `n`, in
+/// ascending order.
+///
+/// Each fraction is formatted as `(numerator, denominator)`.
+///
+/// Rather than enumerating and sorting every fraction, the terms
+/// are generated in order from the two previous terms `a/b` and
+/// `c/d` using the recurrence
+///
+/// ```text
+///  p     k * c - a                   n + b
+/// --- = -----------  where  k = floor(-------)
+///  q     k * d - b                     d
+/// ```
+///
+/// Every term produced this way is already reduced.
+///
+/// If `n` is zero, an empty `Vec` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::farey_sequence;
+/// assert_eq!(farey_sequence(3), vec![(0, 1), (1, 3), (1, 2),
+///                                    (2, 3), (1, 1)]);
+/// ```
+pub fn farey_sequence(n: u64) -> Vec<(u64, u64)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let (mut a, mut b, mut c, mut d) = (0, 1, 1, n);
+    let mut sequence: Vec<(u64, u64)> = vec![(a, b)];
+
+    while c <= n {
+        sequence.push((c, d));
+
+        let k = (n + b) / d;
+        let (p, q) = (k * c - a, k * d - b);
+        a = c;
+        b = d;
+        c = p;
+        d = q;
+    }
+
+    sequence
+}
+
 /// List of least significant bytes for values 
 /// that could be perfect squares.
 pub const GOOD_BYTES: [bool; 256] = 
@@ -187,7 +336,7 @@ pub fn perfect_square(n: u64) -> bool {
 /// Return `true` if `n` is a perfect cube.
 ///
 /// This function works by checking if the digital root of `n`
-/// is equal to zero, one, eight, or nine. If it is not, `n` cannot
+/// is equal to one, eight, or nine. If it is not, `n` cannot
 /// be a perfect cube and the function returns `false`. If the
 /// digital root is a valid number, then the cube root of `n` is taken.
 /// If the root is an integer, then `n` is a perfect cube and `true` is
@@ -205,10 +354,9 @@ pub fn perfect_cube(n: u64) -> bool {
         return true;
     }
 
-    let dr = n - 9 * ((n - 1) as f64 / 9.0) as u64;
-
-    if dr == 0 && dr != 1 && dr != 8 && dr != 9 {
-        return false;
+    match digits::digital_root(n, 10) {
+        1 | 8 | 9 => (),
+        _         => return false,
     }
 
     let root = (n as f64).cbrt();
@@ -220,6 +368,120 @@ pub fn perfect_cube(n: u64) -> bool {
     root_i * root_i * root_i == n
 }
 
+/// Return the integer logarithm of `n` to `base`, that is, the
+/// largest `k` such that `base^k <= n`.
+///
+/// Unlike `(n as f64).log(base as f64)`, the result is computed
+/// exactly using only integer multiplication, so it is never
+/// off by one near powers of `base`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if `base` is less than `2`, as the
+/// logarithm is undefined in these cases.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::ilog;
+/// assert_eq!(ilog(1000, 10), 3);
+/// assert_eq!(ilog(1023, 2), 9);
+/// assert_eq!(ilog(1024, 2), 10);
+/// ```
+pub fn ilog(n: u64, base: u64) -> u32 {
+    assert!(n != 0, "logarithm of zero is undefined!");
+    assert!(base >= 2, "logarithm base {} is less than 2!", base);
+
+    let mut log = 0;
+    let mut pow: u64 = 1;
+    while let Some(next) = pow.checked_mul(base) {
+        if next > n {
+            break;
+        }
+        pow = next;
+        log += 1;
+    }
+
+    log
+}
+
+/// Maximum number of steps `factor_fermat()` will take before
+/// giving up.
+pub const FERMAT_MAX_ITER: u64 = 1_000_000;
+
+/// Find a nontrivial factorization `(p, q)` of `n`, with `p <= q`,
+/// using Fermat's factorization method.
+///
+/// This function searches for an `a` such that `a^2 - n` is a perfect
+/// square `b^2`, starting at `a = ceil(sqrt(n))`, then returns
+///
+/// ```text
+/// (a - b, a + b)
+/// ```
+///
+/// The method is very fast when `n` has two factors close to `sqrt(n)`,
+/// which is exactly the case where `rho()` is slowest. The pair that
+/// is found has the largest `p` possible.
+///
+/// Even values are factored as `(2, n / 2)`, as Fermat's method only
+/// applies to odd values.
+///
+/// `None` is returned if `n` is prime, less than `4`, or if no
+/// factorization is found within `FERMAT_MAX_ITER` steps.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factor_fermat;
+/// assert_eq!(factor_fermat(5959), Some((59, 101)));
+/// assert_eq!(factor_fermat(49), Some((7, 7)));
+/// assert_eq!(factor_fermat(13), None);
+/// ```
+pub fn factor_fermat(n: u64) -> Option<(u64, u64)> {
+    if n < 4 {
+        return None;
+    }
+
+    if n & 0x01 == 0 {
+        return Some((2, n / 2));
+    }
+
+    let mut a = isqrt(n);
+    if a * a < n {
+        a += 1;
+    }
+
+    let n = n as u128;
+    let a = a as u128;
+    for a in a..(a + FERMAT_MAX_ITER as u128) {
+        let b2 = a * a - n;
+        let b = isqrt(b2 as u64) as u128;
+
+        if b * b == b2 {
+            if a - b == 1 {
+                return None;
+            }
+            return Some(((a - b) as u64, (a + b) as u64));
+        }
+    }
+
+    None
+}
+
+// helper function to compute floor(sqrt(n)) exactly
+pub(crate) fn isqrt(n: u64) -> u64 {
+    let n = n as u128;
+    let mut root = (n as f64).sqrt() as u128;
+    while root * root > n {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+
+    root as u64
+}
+
 /// Extract a factor of `val` using `entropy` as a seed
 /// value.
 ///
@@ -299,6 +561,63 @@ pub fn rho(val: u64, entropy: u64) -> u64 {
     fac
 }
 
+/// Return a non-trivial factor of `n` using Pollard's `p - 1`
+/// method, or `None` if no factor is found.
+///
+/// A factor `p` of `n` will be found if every prime factor of
+/// `p - 1` is at most `bound`. Starting with `a = 2`, `a` is
+/// raised to each prime `q <= bound` as many times as `q`
+/// could divide `p - 1`, checking after each step if
+///
+/// ```text
+/// 1 < gcd(a - 1, n) < n
+/// ```
+///
+/// This makes the method very fast on values that `rho()` struggles
+/// with, as long as one of their factors has this form. 
+///
+/// Even values are factored as `2`, and `None` is returned if `n` is
+/// less than `4`, or if every factor of `n` is found at once.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::pollard_p_minus_1;
+/// assert_eq!(pollard_p_minus_1(65_537 * 1_000_000_007, 10), Some(65_537));
+/// assert_eq!(pollard_p_minus_1(1_000_000_007, 1_000), None);
+/// ```
+pub fn pollard_p_minus_1(n: u64, bound: u64) -> Option<u64> {
+    if n < 4 {
+        return None;
+    }
+
+    if n & 0x01 == 0 {
+        return Some(2);
+    }
+
+    let mut a = 2;
+    for q in prime::prime_sieve(bound) {
+        let mut q_pow = q;
+        while q_pow <= n {
+            a = modular::pow_mod(a, q, n);
+
+            let fac = gcd(if a == 0 { n - 1 } else { a - 1 }, n);
+            if fac == n {
+                return None;
+            } else if fac > 1 {
+                return Some(fac);
+            }
+
+            q_pow = match q_pow.checked_mul(q) {
+                Some(val) => val,
+                None => break,
+            };
+        }
+    }
+
+    None
+}
+
 /// The largest number considered "small" by `quick_factorize_wsp()`.
 ///
 /// Values less than this will be factored with `prime::factorize_wp()`,
@@ -344,10 +663,54 @@ pub const MAX_SMALL_NUM: u64 = 65_536;
 /// assert_eq!(quick_factorize_wsp(9_223_372_036_854_775_807, &sprimes), 
 ///            vec![7, 7, 73, 127, 337, 92737, 649657]);
 /// ```
-pub fn quick_factorize_wsp(mut val: u64, 
-                           sprimes: &[u64]) -> Vec<u64> {
-    if val < MAX_SMALL_NUM {
-        return prime::factorize_wp(val, sprimes);
+pub fn quick_factorize_wsp(val: u64, sprimes: &[u64]) -> Vec<u64> {
+    quick_factorize_wsp_cb(val, sprimes, |_| ())
+}
+
+/// Return a `Vec<u64>` of `value`'s prime factorization,
+/// using `sprimes` as a list of small primes, calling `on_factor`
+/// each time a prime factor is found.
+///
+/// This function works exactly like `quick_factorize_wsp()`, see
+/// the documentation of that function for more information.
+///
+/// `on_factor` is called once for every prime factor, including
+/// repeated factors, in the order they are found, which is not
+/// necessarily sorted. This is useful for reporting progress when
+/// factoring values that take a long time.
+///
+/// The factor list this function returns is sorted.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::*;
+/// use reikna::prime;
+/// let sprimes = prime::prime_sieve(MAX_SMALL_NUM);
+/// let mut found = 0;
+/// let factors = quick_factorize_wsp_cb(1_000_000, &sprimes, |_| found += 1);
+/// assert_eq!(found, factors.len());
+/// ```
+pub fn quick_factorize_wsp_cb<F>(val: u64, sprimes: &[u64], 
+                                 mut on_factor: F) -> Vec<u64>
+    where F: FnMut(u64) {
+    let mut factors = qf_helper(val, sprimes, MAX_SMALL_NUM,
+                                &mut |f, _| on_factor(f));
+    factors.sort();
+    factors
+}
+
+// helper function for quick_factorize_wsp_cb(), returns unsorted factors,
+// calling `on_factor` with each factor and whether it was found by
+// trial division. `sprimes` must hold the primes less than `small_bound`
+fn qf_helper(mut val: u64, sprimes: &[u64], small_bound: u64,
+             on_factor: &mut dyn FnMut(u64, bool)) -> Vec<u64> {
+    if val < small_bound || val == 0 {
+        let factors = prime::factorize_wp(val, sprimes);
+        for factor in &factors {
+            on_factor(*factor, true);
+        }
+        return factors;
     }
 
     let mut factors: Vec<u64> = Vec::with_capacity(64);
@@ -355,12 +718,14 @@ pub fn quick_factorize_wsp(mut val: u64,
     while val & 0x01 == 0 {
         val >>= 1;
         factors.push(2);
+        on_factor(2, true);
     }
 
     let mut e = 2;
     while val > 1 {
         if prime::is_prime(val) {
             factors.push(val);
+            on_factor(val, false);
             break;
         }
 
@@ -371,15 +736,15 @@ pub fn quick_factorize_wsp(mut val: u64,
             continue;
         } else if prime::is_prime(factor) {
             factors.push(factor);
+            on_factor(factor, false);
         } else {
             factors.extend_from_slice(
-                   &quick_factorize_wsp(factor, sprimes));
+                   &qf_helper(factor, sprimes, small_bound, on_factor));
         }
 
         val /= factor;
     }
 
-    factors.sort();
     factors
 }
 
@@ -390,9 +755,9 @@ pub fn quick_factorize_wsp(mut val: u64,
 /// See `quick_factorize_wsp()` for more information.
 ///
 /// Because this function generates a list of primes each time it
-/// is called, it is preferable to use `quick_factorize_wsp()` 
-/// directly with an explicit list of primes if numerous factorizations
-/// are being computed.
+/// is called, it is preferable to use a `Factorizer`, or to use
+/// `quick_factorize_wsp()` directly with an explicit list of primes,
+/// if numerous factorizations are being computed.
 ///
 /// # Panics
 ///
@@ -411,74 +776,998 @@ pub fn quick_factorize(value: u64) -> Vec<u64> {
     quick_factorize_wsp(value, &prime::prime_sieve(MAX_SMALL_NUM))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use super::super::prime::is_prime;
+/// Return a `Vec<u64>` of `value`'s prime factorization, calling
+/// `on_factor` each time a prime factor is found.
+///
+/// This is a helper function that calls `quick_factorize_wsp_cb()`,
+/// using a generated list of primes. See `quick_factorize_wsp_cb()`
+/// for more information.
+///
+/// # Panics
+///
+/// Panics if `prime_sieve()`, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::quick_factorize_cb;
+/// let factors = quick_factorize_cb(9_223_372_036_854_775_807, 
+///                                  |f| println!("found factor {}", f));
+/// assert_eq!(factors, vec![7, 7, 73, 127, 337, 92737, 649657]);
+/// ```
+pub fn quick_factorize_cb<F>(value: u64, on_factor: F) -> Vec<u64>
+    where F: FnMut(u64) {
+    quick_factorize_wsp_cb(value, &prime::prime_sieve(MAX_SMALL_NUM), 
+                           on_factor)
+}
 
-#[test]
-    fn t_gcd() {
-        assert_eq!(gcd(0, 0), 0);
-        assert_eq!(gcd(0, 10), 10);
-        assert_eq!(gcd(10, 0), 10);
-        assert_eq!(gcd(24, 12), 12);
-        assert_eq!(gcd(8, 12), 4);
-        assert_eq!(gcd(5125215, 890898), 3);
-        assert_eq!(gcd(5125215, 890898), 3);
-    }
+/// Return a `Vec<u64>` of `value`'s prime factorization, using
+/// `small_bound` in place of `MAX_SMALL_NUM`.
+///
+/// This function works exactly like `quick_factorize()`, but sieves
+/// the primes up to `small_bound`, and factors values less than 
+/// `small_bound` by trial division. A larger bound makes each call 
+/// slower, as more primes are sieved, but may suit data sets with
+/// many medium sized factors. The factorization is the same for
+/// every bound, only the performance differs.
+///
+/// The factor list this function returns is sorted.
+///
+/// # Panics
+///
+/// Panics if `prime_sieve()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::quick_factorize_with_bound;
+/// assert_eq!(quick_factorize_with_bound(360, 0), vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(quick_factorize_with_bound(999_985_999_949, 1_000_000),
+///            vec![999_983, 1_000_003]);
+/// ```
+pub fn quick_factorize_with_bound(value: u64, small_bound: u64) -> Vec<u64> {
+    let mut factors = qf_helper(value, &prime::prime_sieve(small_bound),
+                                small_bound, &mut |_, _| ());
+    factors.sort();
+    factors
+}
 
-#[test]
-    fn t_gcd_all() {
-        assert_eq!(gcd_all(&vec![]), 0);
-        assert_eq!(gcd_all(&vec![0, 0, 0]), 0);
-        assert_eq!(gcd_all(&vec![0, 1, 0, 1]), 1);
-        assert_eq!(gcd_all(&vec![0, 2, 6, 8]), 2);
-        assert_eq!(gcd_all(&vec![1, 2, 3, 4]), 1);
-        assert_eq!(gcd_all(&vec![9, 27, 81]), 9);
-        assert_eq!(gcd_all(&vec![2, 4, 6, 8]), 2);
-    }
+/// Return `true` if `factors` is the prime factorization of `value`,
+/// that is, if every factor is prime and their product is `value`.
+///
+/// Each factor is checked with `prime::is_prime_bpsw()`, which is exact
+/// for every `u64`, so even large factors are checked quickly.
+///
+/// The product is computed with checked multiplication, so a list of
+/// factors whose product overflows a `u64` is never accepted. The
+/// factors may be given in any order.
+///
+/// Zero has no prime factorization, so `false` is always returned
+/// for zero. The factorization of one is the empty list.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::{quick_factorize, verify_factorization};
+/// let factors = quick_factorize(9_223_372_036_854_775_807);
+/// assert_eq!(verify_factorization(9_223_372_036_854_775_807, &factors),
+///            true);
+/// assert_eq!(verify_factorization(12, &[2, 6]), false);
+/// ```
+pub fn verify_factorization(value: u64, factors: &[u64]) -> bool {
+    let mut product: u64 = 1;
+    for factor in factors {
+        if !prime::is_prime_bpsw(*factor) {
+            return false;
+        }
 
-#[test]
-    fn t_coprime() {
-        assert_eq!(coprime(0, 0), false);
-        assert_eq!(coprime(1, 0), true);
-        assert_eq!(coprime(1, 10), true);
-        assert_eq!(coprime(4, 9), true);
-        assert_eq!(coprime(12, 9), false);
+        product = match product.checked_mul(*factor) {
+            Some(product) => product,
+            None => return false,
+        };
     }
 
-#[test]
-    fn t_lcm() {
-        assert_eq!(lcm(0, 0), 0);
-        assert_eq!(lcm(0, 15), 0);
-        assert_eq!(lcm(5, 2), 10);
-        assert_eq!(lcm(13, 5), 65);
-        assert_eq!(lcm(1, 35), 35);
-    }
+    product == value
+}
 
-#[test]
-    fn t_lcm_all() {
-        assert_eq!(lcm_all(&vec![]), 1);
-        assert_eq!(lcm_all(&vec![0, 0, 0]), 0);
-        assert_eq!(lcm_all(&vec![0, 1, 2, 3]), 0);
-        assert_eq!(lcm_all(&vec![1, 2, 3, 4]), 12);
-        assert_eq!(lcm_all(&vec![2, 2, 2]), 2);
-    }
+/// A report on how the factors of a value were found by
+/// `quick_factorize()`, see `factorize_report()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FactorReport {
+    /// The sorted prime factorization of the value.
+    pub factors: Vec<u64>,
+    /// The number of factors found by trial division, either because
+    /// they were left in a value less than `MAX_SMALL_NUM`, or because
+    /// they were factors of two removed from a larger value.
+    pub trial_division: usize,
+    /// The number of factors found while factoring values larger than
+    /// `MAX_SMALL_NUM` with `rho()`, including any remaining cofactor
+    /// that was found to be prime.
+    pub rho: usize,
+}
 
-#[test]
-    fn t_perfect_square() {
-        assert_eq!(perfect_square(0), true);
-        assert_eq!(perfect_square(1), true);
-        assert_eq!(perfect_square(2), false);
-        assert_eq!(perfect_square(7), false);
-        assert_eq!(perfect_square(15), false);
-        assert_eq!(perfect_square(144), true);
-        assert_eq!(perfect_square(145), false);
-        assert_eq!(perfect_square(1_073_741_824), true);
-        assert_eq!(perfect_square(1_073_741_823), false);
-        assert_eq!(perfect_square(4_611_686_018_427_387_904), true);
-        assert_eq!(perfect_square(4_611_686_018_427_387_905), false);
-    }
+/// Return a `FactorReport` of `value`'s prime factorization, recording
+/// how many factors were found by trial division, and how many were
+/// found using Pollard's Rho.
+///
+/// The factors are found in exactly the same way as `quick_factorize()`,
+/// so the report is useful for understanding its performance on a data
+/// set, and for judging whether `MAX_SMALL_NUM` suits it.
+///
+/// # Panics
+///
+/// Panics if `prime_sieve()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factorize_report;
+/// let report = factorize_report(360);
+/// assert_eq!(report.factors, vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(report.trial_division, 6);
+/// assert_eq!(report.rho, 0);
+///
+/// let report = factorize_report(999_985_999_949);
+/// assert_eq!(report.factors, vec![999_983, 1_000_003]);
+/// assert_eq!(report.rho, 2);
+/// ```
+pub fn factorize_report(value: u64) -> FactorReport {
+    let mut trial_division = 0;
+    let mut rho = 0;
+    let mut count = |_, by_trial| if by_trial {
+        trial_division += 1;
+    } else {
+        rho += 1;
+    };
+
+    let mut factors = qf_helper(value, &prime::prime_sieve(MAX_SMALL_NUM),
+                                MAX_SMALL_NUM, &mut count);
+    factors.sort();
+
+    FactorReport { factors, trial_division, rho }
+}
+
+/// A factorizer that keeps its list of small primes between calls.
+///
+/// `quick_factorize()` generates a list of the primes less than
+/// `MAX_SMALL_NUM` each time it is called, which dominates the cost
+/// of factoring small values. A `Factorizer` generates this list once
+/// when it is created, and reuses it for every factorization.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::Factorizer;
+/// let factorizer = Factorizer::new();
+/// assert_eq!(factorizer.factorize(360), vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(factorizer.factorize(9_223_372_036_854_775_807), 
+///            vec![7, 7, 73, 127, 337, 92737, 649657]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Factorizer {
+    sprimes: Vec<u64>,
+}
+
+impl Factorizer {
+    /// Return a new `Factorizer`, generating the list of small primes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prime_sieve()` panics, see the documentation for
+    /// this function for more information.
+    pub fn new() -> Factorizer {
+        Factorizer { sprimes: prime::prime_sieve(MAX_SMALL_NUM) }
+    }
+
+    /// Return a `Vec<u64>` of `value`'s prime factorization.
+    ///
+    /// This calls `quick_factorize_wsp()` with the stored list of
+    /// small primes, see `quick_factorize_wsp()` for more information.
+    pub fn factorize(&self, value: u64) -> Vec<u64> {
+        quick_factorize_wsp(value, &self.sprimes)
+    }
+}
+
+impl Default for Factorizer {
+    fn default() -> Factorizer {
+        Factorizer::new()
+    }
+}
+
+/// A node in the factor tree of a value, see `factor_tree()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FactorNode {
+    /// The value factored at this step.
+    pub value: u64,
+    /// The factor extracted from `value`, or `None` if `value` 
+    /// is prime, zero, or one.
+    pub factor: Option<u64>,
+    /// The subtrees of `factor` and `value / factor`, in that order,
+    /// or an empty `Vec` if no factor was extracted.
+    pub children: Vec<FactorNode>,
+}
+
+impl FactorNode {
+    /// Return a `Vec<u64>` of the prime leaves of this tree, from
+    /// left to right.
+    ///
+    /// Leaves with a value of zero or one are not included, so
+    /// the result is the same multiset as `quick_factorize(value)`.
+    pub fn leaves(&self) -> Vec<u64> {
+        let mut leaves: Vec<u64> = Vec::new();
+        self.collect_leaves(&mut leaves);
+        leaves
+    }
+
+    fn collect_leaves(&self, leaves: &mut Vec<u64>) {
+        if self.children.is_empty() {
+            if self.value > 1 {
+                leaves.push(self.value);
+            }
+            return;
+        }
+
+        for child in &self.children {
+            child.collect_leaves(leaves);
+        }
+    }
+}
+
+/// Return the factor tree of `value`, recording each factor found
+/// while factoring `value`.
+///
+/// This mirrors the steps taken by `quick_factorize()`: at each step
+/// factors of two are removed first, then values smaller than 
+/// `MAX_SMALL_NUM` are split by trial division, and larger values are
+/// split using `rho()`. Each composite node has two children, the
+/// subtrees of the extracted factor and the remaining cofactor, and
+/// the leaves of the tree are the prime factors of `value`.
+///
+/// This function is meant for visualizing the factorization, use
+/// `quick_factorize()` if only the factors are needed.
+///
+/// # Panics
+///
+/// Panics if `prime_sieve()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factor_tree;
+/// let tree = factor_tree(12);
+/// assert_eq!(tree.factor, Some(2));
+/// assert_eq!(tree.children[1].value, 6);
+/// assert_eq!(tree.leaves(), vec![2, 2, 3]);
+/// ```
+pub fn factor_tree(value: u64) -> FactorNode {
+    ft_helper(value, &prime::prime_sieve(MAX_SMALL_NUM))
+}
+
+// helper function to recursively build a factor tree
+fn ft_helper(val: u64, sprimes: &[u64]) -> FactorNode {
+    if val < 2 || prime::is_prime(val) {
+        return FactorNode { value: val, factor: None, children: Vec::new() };
+    }
+
+    let factor = if val & 0x01 == 0 {
+        2
+    } else if val < MAX_SMALL_NUM {
+        *sprimes.iter().find(|p| val % *p == 0).unwrap()
+    } else {
+        let mut e = 2;
+        loop {
+            let factor = rho(val, e);
+            if factor != val && factor != 1 {
+                break factor;
+            }
+            e += 1;
+        }
+    };
+
+    FactorNode {
+        value: val,
+        factor: Some(factor),
+        children: vec![ft_helper(factor, sprimes), 
+                       ft_helper(val / factor, sprimes)],
+    }
+}
+
+/// Return `value`'s prime factorization as a `Vec<(u64, u32)>` of
+/// `(prime, exponent)` pairs, sorted by prime.
+///
+/// This is a helper function that calls `quick_factorize()` and
+/// groups equal factors together. See `quick_factorize()` for
+/// more information.
+///
+/// # Panics
+///
+/// Panics if `quick_factorize()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factorize_exp;
+/// assert_eq!(factorize_exp(1), vec![]);
+/// assert_eq!(factorize_exp(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// ```
+pub fn factorize_exp(value: u64) -> Vec<(u64, u32)> {
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in quick_factorize(value) {
+        match factors.last_mut() {
+            Some(&mut (q, ref mut e)) if q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+
+    factors
+}
+
+/// Return `value`'s prime factorization as a `HashMap<u64, u32>` mapping
+/// each prime factor to its exponent.
+///
+/// This is built from `factorize_exp()`, see `factorize_exp()` for
+/// more information. Primes that do not divide `value` are not in
+/// the map.
+///
+/// # Panics
+///
+/// Panics if `quick_factorize()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factor_map;
+/// let map = factor_map(360);
+/// assert_eq!(map[&2], 3);
+/// assert_eq!(map.get(&3), Some(&2));
+/// assert_eq!(map.get(&7), None);
+/// ```
+pub fn factor_map(value: u64) -> HashMap<u64, u32> {
+    factorize_exp(value).into_iter().collect()
+}
+
+/// Return a sorted `Vec<u64>` of every positive divisor of `n`,
+/// including `1` and `n` itself.
+///
+/// The divisors are generated from the factorization of `n` given
+/// by `factorize_exp()`, rather than by trial division. If `n` is 
+/// zero, an empty `Vec` is returned.
+///
+/// # Panics
+///
+/// Panics if `factorize_exp()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::divisors;
+/// assert_eq!(divisors(1), vec![1]);
+/// assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+/// ```
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut divs: Vec<u64> = vec![1];
+    for (p, e) in factorize_exp(n) {
+        let count = divs.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..count {
+                divs.push(divs[i] * pk);
+            }
+        }
+    }
+
+    divs.sort();
+    divs
+}
+
+/// Return the number of positive divisors of `n` for which `pred`
+/// returns `true`.
+///
+/// Like `divisors()`, the divisors are generated from the factorization
+/// of `n` given by `factorize_exp()`, but they are passed to `pred` one
+/// at a time rather than being collected, in no particular order.
+///
+/// If `n` is zero, `0` is returned.
+///
+/// # Panics
+///
+/// Panics if `factorize_exp()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::{count_divisors_where, perfect_square};
+/// assert_eq!(count_divisors_where(12, |d| d % 2 == 1), 2);
+/// assert_eq!(count_divisors_where(36, perfect_square), 4);
+/// ```
+pub fn count_divisors_where<F>(n: u64, pred: F) -> u64
+    where F: Fn(u64) -> bool {
+    if n == 0 {
+        return 0;
+    }
+
+    let factors = factorize_exp(n);
+    let mut exps: Vec<u32> = vec![0; factors.len()];
+
+    let mut count = 0;
+    let mut d = 1;
+    loop {
+        if pred(d) {
+            count += 1;
+        }
+
+        // step to the next divisor, like an odometer over the exponents
+        let mut i = 0;
+        while i < factors.len() {
+            let (p, e) = factors[i];
+            if exps[i] < e {
+                exps[i] += 1;
+                d *= p;
+                break;
+            }
+
+            d /= p.pow(e);
+            exps[i] = 0;
+            i += 1;
+        }
+
+        if i == factors.len() {
+            return count;
+        }
+    }
+}
+
+/// Return an iterator over each value `n` in `[2, max]`, paired
+/// with its prime factorization.
+///
+/// The factorizations are in the same form as those returned by
+/// `factorize_exp()`, but instead of factoring each value independently,
+/// they are read from a smallest-prime-factor sieve built with a linear
+/// sieve. The sieve is built when this function is called, so it
+/// requires `O(max)` memory up front.
+///
+/// # Panics
+///
+/// Panics if `max` is too large to index a sieve of size `max + 1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::factorizations;
+/// let facs: Vec<_> = factorizations(6).collect();
+/// assert_eq!(facs, vec![(2, vec![(2, 1)]),
+///                       (3, vec![(3, 1)]),
+///                       (4, vec![(2, 2)]),
+///                       (5, vec![(5, 1)]),
+///                       (6, vec![(2, 1), (3, 1)])]);
+/// ```
+pub fn factorizations(max: u64) -> impl Iterator<Item = (u64, Vec<(u64, u32)>)> {
+    assert!(max < usize::MAX as u64, "max is too large to sieve!");
+
+    let spf = spf_sieve(max);
+    (2..max.saturating_add(1)).map(move |n| {
+        let mut factors: Vec<(u64, u32)> = Vec::new();
+        let mut val = n;
+        while val > 1 {
+            let p = spf[val as usize];
+            let mut e = 0;
+            while val % p == 0 {
+                val /= p;
+                e += 1;
+            }
+            factors.push((p, e));
+        }
+
+        (n, factors)
+    })
+}
+
+// helper function to compute the smallest prime factor of every
+// value in [0, max] with a linear sieve, 0 and 1 are left as 0
+fn spf_sieve(max: u64) -> Vec<u64> {
+    let mut spf = vec![0u64; max as usize + 1];
+    let mut primes: Vec<u64> = Vec::new();
+
+    for i in 2..spf.len() {
+        if spf[i] == 0 {
+            spf[i] = i as u64;
+            primes.push(i as u64);
+        }
+
+        for &p in &primes {
+            if p > spf[i] || i as u64 * p > max {
+                break;
+            }
+            spf[i * p as usize] = p;
+        }
+    }
+
+    spf
+}
+
+/// Return a `Vec<i8>` of the Möbius function of every value in
+/// [0, max], that is, `μ(0), μ(1), ..., μ(max)`.
+///
+/// `μ(n)` is `0` if `n` is divisible by a square greater than one,
+/// and otherwise `(-1)^k`, where `k` is the number of prime factors
+/// of `n`. The values are read from the same smallest-prime-factor
+/// sieve as `factorizations()`, using
+///
+/// ```text
+/// μ(n) = 0       if p^2 divides n
+/// μ(n) = -μ(n/p) otherwise
+/// ```
+///
+/// where `p` is the smallest prime factor of `n`. `μ(0)` is `0`.
+///
+/// # Panics
+///
+/// Panics if `max` is too large to index a sieve of size `max + 1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::mobius_sieve;
+/// assert_eq!(mobius_sieve(10), vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+/// ```
+pub fn mobius_sieve(max: u64) -> Vec<i8> {
+    assert!(max < usize::MAX as u64, "max is too large to sieve!");
+
+    let spf = spf_sieve(max);
+    let mut mobius = vec![0i8; max as usize + 1];
+    if max >= 1 {
+        mobius[1] = 1;
+    }
+
+    for n in 2..mobius.len() {
+        let p = spf[n] as usize;
+        let m = n / p;
+        mobius[n] = if m % p == 0 { 0 } else { -mobius[m] };
+    }
+
+    mobius
+}
+
+/// Return the value of the Mertens function of `n`, that is, the
+/// sum of the Möbius function over [1, n].
+///
+/// ```text
+/// M(n) = μ(1) + μ(2) + ... + μ(n)
+/// ```
+///
+/// The values of `μ` are computed all at once with `mobius_sieve()`.
+///
+/// # Panics
+///
+/// Panics if `mobius_sieve()` panics, see the documentation for
+/// this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::mertens;
+/// assert_eq!(mertens(10), -1);
+/// assert_eq!(mertens(100), 1);
+/// ```
+pub fn mertens(n: u64) -> i64 {
+    mobius_sieve(n).iter().map(|&mu| mu as i64).sum()
+}
+
+/// Return the value of Liouville's function of `n`, that is,
+/// `(-1)^Ω(n)`, where `Ω(n)` is the number of prime factors of `n`
+/// counted with multiplicity.
+///
+/// `Ω(n)` is found from the factorization of `n` given by
+/// `factorize_exp()`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::liouville;
+/// assert_eq!(liouville(4), 1);
+/// assert_eq!(liouville(12), -1);
+/// ```
+pub fn liouville(n: u64) -> i8 {
+    assert!(n != 0, "Liouville's function is undefined for zero!");
+
+    let big_omega: u32 = factorize_exp(n).iter().map(|f| f.1).sum();
+    if big_omega & 0x01 == 0 { 1 } else { -1 }
+}
+
+/// Return the sum of Liouville's function over [1, n].
+///
+/// ```text
+/// L(n) = λ(1) + λ(2) + ... + λ(n)
+/// ```
+///
+/// Rather than factoring each value, the parity of `Ω(k)` is read
+/// from a smallest-prime-factor sieve, as `Ω(k) = Ω(k/p) + 1` where
+/// `p` is the smallest prime factor of `k`.
+///
+/// # Panics
+///
+/// Panics if `n` is too large to index a sieve of size `n + 1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::liouville_summatory;
+/// assert_eq!(liouville_summatory(10), 0);
+/// assert_eq!(liouville_summatory(100), -2);
+/// ```
+pub fn liouville_summatory(n: u64) -> i64 {
+    assert!(n < usize::MAX as u64, "n is too large to sieve!");
+
+    let spf = spf_sieve(n);
+    let mut liouville = vec![1i8; n as usize + 1];
+    let mut sum = if n >= 1 { 1 } else { 0 };
+    for k in 2..liouville.len() {
+        liouville[k] = -liouville[k / spf[k] as usize];
+        sum += liouville[k] as i64;
+    }
+
+    sum
+}
+
+/// Return `true` if every prime factor of `n` is at most `bound`,
+/// that is, if `n` is `bound`-smooth.
+///
+/// The factors up to `bound` are divided out of `n` by trial division,
+/// stopping once the remaining value is `1`, or once the remaining value
+/// can have no factor smaller than `bound`, in which case it is itself a
+/// prime that is checked against `bound`.
+///
+/// `1` is smooth for every `bound`, and `0` is never smooth.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::is_smooth;
+/// assert_eq!(is_smooth(720, 5), true);
+/// assert_eq!(is_smooth(14, 3), false);
+/// ```
+pub fn is_smooth(n: u64, bound: u64) -> bool {
+    if n == 0 {
+        return false;
+    }
+
+    let mut val = n;
+    let mut fac = 2;
+    while fac <= bound && fac <= val / fac {
+        while val % fac == 0 {
+            val /= fac;
+        }
+        fac += if fac == 2 { 1 } else { 2 };
+    }
+
+    val <= bound || val == 1
+}
+
+/// Return a `Vec<u64>` of the first `count` Hamming numbers, that
+/// is, the numbers whose only prime factors are `2`, `3`, and `5`,
+/// in increasing order.
+///
+/// The values are generated by merging the multiples of the previous
+/// values by `2`, `3`, and `5`, keeping one index into the output
+/// for each of the three multipliers.
+///
+/// # Panics
+///
+/// Panics if the `count`th Hamming number is larger than `u64::MAX`,
+/// there are only `13,282` Hamming numbers in the range of a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::hamming_numbers;
+/// assert_eq!(hamming_numbers(10), vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12]);
+/// ```
+pub fn hamming_numbers(count: usize) -> Vec<u64> {
+    let mut hamming: Vec<u64> = Vec::with_capacity(count);
+    if count == 0 {
+        return hamming;
+    }
+    hamming.push(1);
+
+    let mut idx = [0usize; 3];
+    let mult = [2u128, 3, 5];
+    while hamming.len() < count {
+        let next = (0..3).map(|i| hamming[idx[i]] as u128 * mult[i])
+                         .min()
+                         .unwrap();
+        assert!(next <= u64::MAX as u128, 
+                "Hamming number {} is larger than u64::MAX!", hamming.len());
+
+        for i in 0..3 {
+            if hamming[idx[i]] as u128 * mult[i] == next {
+                idx[i] += 1;
+            }
+        }
+        hamming.push(next as u64);
+    }
+
+    hamming
+}
+
+/// Return the number of multiplicative partitions of `n`, that is,
+/// the number of ways to write `n` as an unordered product of
+/// integers greater than one.
+///
+/// `n` itself counts as a product with a single term, so the result
+/// is `1` for `n = 1` and for prime `n`. If `n` is a power of a prime,
+/// the result is the partition function of its exponent.
+///
+/// The count is built up over the divisors of `n`, so the running time
+/// is quadratic in the number of divisors of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, which has infinitely many factorizations.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::multiplicative_partitions;
+/// assert_eq!(multiplicative_partitions(1), 1);
+/// assert_eq!(multiplicative_partitions(12), 4);
+/// assert_eq!(multiplicative_partitions(16), 5);
+/// ```
+pub fn multiplicative_partitions(n: u64) -> u64 {
+    assert!(n != 0, "zero has infinitely many factorizations!");
+
+    let divs = divisors(n);
+
+    // ways[i] is the number of ways to write divs[i] as a product
+    // of the divisors considered so far
+    let mut ways: Vec<u64> = vec![0; divs.len()];
+    ways[0] = 1;
+    for (k, d) in divs.iter().enumerate().skip(1) {
+        for i in k..divs.len() {
+            if divs[i] % d == 0 {
+                let j = divs.binary_search(&(divs[i] / d)).unwrap();
+                ways[i] += ways[j];
+            }
+        }
+    }
+
+    ways[divs.len() - 1]
+}
+
+/// Return a `Vec` of every pair `(a, b)` with `a <= b` and
+/// `a^2 + b^2 = n`, sorted by `a`.
+///
+/// By Fermat's theorem on sums of two squares, `n` has a representation
+/// exactly when every prime factor `p = 3 mod 4` of `n` appears to an
+/// even power, and the number of representations depends on the prime
+/// factors `p = 1 mod 4`. The pairs themselves are found by testing
+/// every `a` up to `sqrt(n / 2)`, checking if `n - a^2` is a perfect
+/// square, so this function takes time proportional to `sqrt(n)`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::sum_of_two_squares;
+/// assert_eq!(sum_of_two_squares(25), vec![(0, 5), (3, 4)]);
+/// assert_eq!(sum_of_two_squares(50), vec![(1, 7), (5, 5)]);
+/// assert_eq!(sum_of_two_squares(3), vec![]);
+/// ```
+pub fn sum_of_two_squares(n: u64) -> Vec<(u64, u64)> {
+    let mut pairs: Vec<(u64, u64)> = Vec::new();
+
+    let mut a: u64 = 0;
+    while a * a <= n / 2 {
+        let rest = n - a * a;
+        let b = isqrt(rest);
+        if b * b == rest {
+            pairs.push((a, b));
+        }
+        a += 1;
+    }
+
+    pairs
+}
+
+/// Return `r2(n)`, the number of ways to write `n` as a sum of two
+/// squares, counting the order and signs of the roots.
+///
+/// By Jacobi's two-square theorem,
+///
+/// ```text
+/// r2(n) = 4 * (d1(n) - d3(n))
+/// ```
+///
+/// where `d1(n)` and `d3(n)` are the number of divisors of `n` equal
+/// to `1` and `3` modulo `4`. From the factorization of `n`, this is
+/// zero if any prime factor `p = 3 mod 4` appears to an odd power, and
+/// otherwise four times the product of `e + 1` over the prime powers
+/// `p^e` of `n` with `p = 1 mod 4`.
+///
+/// `r2(0)` is one, as `0 = 0^2 + 0^2`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::factor::count_sum_of_two_squares;
+/// assert_eq!(count_sum_of_two_squares(5), 8); // (±1)^2 + (±2)^2, ...
+/// assert_eq!(count_sum_of_two_squares(25), 12);
+/// assert_eq!(count_sum_of_two_squares(3), 0);
+/// ```
+pub fn count_sum_of_two_squares(n: u64) -> u64 {
+    if n == 0 {
+        return 1;
+    }
+
+    let mut count = 4;
+    for (p, e) in factorize_exp(n) {
+        match p % 4 {
+            1 => count *= e as u64 + 1,
+            3 if e & 0x01 == 1 => return 0,
+            _ => (),
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::prime::is_prime;
+    use super::super::totient::totient;
+
+#[test]
+    fn t_gcd() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 10), 10);
+        assert_eq!(gcd(10, 0), 10);
+        assert_eq!(gcd(24, 12), 12);
+        assert_eq!(gcd(8, 12), 4);
+        assert_eq!(gcd(5125215, 890898), 3);
+        assert_eq!(gcd(5125215, 890898), 3);
+    }
+
+#[test]
+    fn t_gcd_ext() {
+        assert_eq!(gcd_ext(0, 0), (0, 1, 0));
+        assert_eq!(gcd_ext(0, 10), (10, 0, 1));
+        assert_eq!(gcd_ext(10, 0), (10, 1, 0));
+        assert_eq!(gcd_ext(240, 46), (2, -9, 47));
+
+        let vals = vec![1, 2, 12, 35, 97, 1_000, 65_536, 5_125_215, 890_898,
+                        u64::MAX, u64::MAX - 1, 1 << 63];
+        for a in vals.iter() {
+            for b in vals.iter() {
+                let (g, x, y) = gcd_ext(*a, *b);
+                assert_eq!(g, gcd(*a, *b));
+                assert_eq!(*a as i128 * x + *b as i128 * y, g as i128);
+            }
+        }
+    }
+
+#[test]
+    fn t_gcd_all() {
+        assert_eq!(gcd_all(&vec![]), 0);
+        assert_eq!(gcd_all(&vec![0, 0, 0]), 0);
+        assert_eq!(gcd_all(&vec![0, 1, 0, 1]), 1);
+        assert_eq!(gcd_all(&vec![0, 2, 6, 8]), 2);
+        assert_eq!(gcd_all(&vec![1, 2, 3, 4]), 1);
+        assert_eq!(gcd_all(&vec![9, 27, 81]), 9);
+        assert_eq!(gcd_all(&vec![2, 4, 6, 8]), 2);
+    }
+
+#[test]
+    fn t_coprime() {
+        assert_eq!(coprime(0, 0), false);
+        assert_eq!(coprime(1, 0), true);
+        assert_eq!(coprime(1, 10), true);
+        assert_eq!(coprime(4, 9), true);
+        assert_eq!(coprime(12, 9), false);
+    }
+
+#[test]
+    fn t_lcm() {
+        assert_eq!(lcm(0, 0), 0);
+        assert_eq!(lcm(0, 15), 0);
+        assert_eq!(lcm(5, 2), 10);
+        assert_eq!(lcm(13, 5), 65);
+        assert_eq!(lcm(1, 35), 35);
+    }
+
+#[test]
+    fn t_lcm_all() {
+        assert_eq!(lcm_all(&vec![]), 1);
+        assert_eq!(lcm_all(&vec![0, 0, 0]), 0);
+        assert_eq!(lcm_all(&vec![0, 1, 2, 3]), 0);
+        assert_eq!(lcm_all(&vec![1, 2, 3, 4]), 12);
+        assert_eq!(lcm_all(&vec![2, 2, 2]), 2);
+    }
+
+#[test]
+    fn t_checked_lcm() {
+        assert_eq!(checked_lcm(0, 0), Some(0));
+        assert_eq!(checked_lcm(0, 15), Some(0));
+        assert_eq!(checked_lcm(5, 2), Some(10));
+        assert_eq!(checked_lcm(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(checked_lcm(u64::MAX, 2), None);
+
+        for a in 0..50 {
+            for b in 0..50 {
+                assert_eq!(checked_lcm(a, b), Some(lcm(a, b)));
+            }
+        }
+
+        assert_eq!(checked_lcm_all(&[]), Some(1));
+        assert_eq!(checked_lcm_all(&[0, 1, 2, 3]), Some(0));
+        assert_eq!(checked_lcm_all(&[1, 2, 3, 4]), Some(12));
+
+        let set: Vec<u64> = (1..21).collect();
+        assert_eq!(checked_lcm_all(&set), Some(232_792_560));
+
+        let set: Vec<u64> = (1..47).collect();
+        assert_eq!(checked_lcm_all(&set), Some(9_419_588_158_802_421_600));
+
+        let set: Vec<u64> = (1..48).collect();
+        assert_eq!(checked_lcm_all(&set), None);
+        assert_eq!(checked_lcm_all(&[1 << 63, 3]), None);
+    }
+
+#[test]
+    fn t_farey_sequence() {
+        assert_eq!(farey_sequence(0), vec![]);
+        assert_eq!(farey_sequence(1), vec![(0, 1), (1, 1)]);
+        assert_eq!(farey_sequence(5), vec![(0, 1), (1, 5), (1, 4), (1, 3),
+                                           (2, 5), (1, 2), (3, 5), (2, 3),
+                                           (3, 4), (4, 5), (1, 1)]);
+
+        for n in 1..30 {
+            let sequence = farey_sequence(n);
+
+            let phi_sum = (1..(n + 1)).map(totient).sum::<u64>();
+            assert_eq!(sequence.len() as u64, 1 + phi_sum);
+
+            for w in sequence.windows(2) {
+                let ((a, b), (c, d)) = (w[0], w[1]);
+                assert_eq!(gcd(c, d), 1);
+                assert!(d <= n);
+                assert_eq!(c * b - a * d, 1);
+            }
+        }
+    }
+
+#[test]
+    fn t_perfect_square() {
+        assert_eq!(perfect_square(0), true);
+        assert_eq!(perfect_square(1), true);
+        assert_eq!(perfect_square(2), false);
+        assert_eq!(perfect_square(7), false);
+        assert_eq!(perfect_square(15), false);
+        assert_eq!(perfect_square(144), true);
+        assert_eq!(perfect_square(145), false);
+        assert_eq!(perfect_square(1_073_741_824), true);
+        assert_eq!(perfect_square(1_073_741_823), false);
+        assert_eq!(perfect_square(4_611_686_018_427_387_904), true);
+        assert_eq!(perfect_square(4_611_686_018_427_387_905), false);
+    }
 
 #[test]
     fn t_perfect_cube() {
@@ -496,6 +1785,116 @@ mod tests {
         assert_eq!(perfect_cube(8_589_934_593), false);
         assert_eq!(perfect_cube(11_529_2150_460_6846_976), true);
         assert_eq!(perfect_cube(11_529_2150_460_6846_975), false);
+
+        for n in 0..100_000 {
+            let root = (n as f64).cbrt().round() as u64;
+            assert_eq!(perfect_cube(n), root * root * root == n);
+        }
+    }
+
+#[test]
+    fn t_ilog() {
+        assert_eq!(ilog(1, 2), 0);
+        assert_eq!(ilog(9, 10), 0);
+        assert_eq!(ilog(10, 10), 1);
+        assert_eq!(ilog(1000, 10), 3);
+        assert_eq!(ilog(999, 10), 2);
+        assert_eq!(ilog(1023, 2), 9);
+        assert_eq!(ilog(1024, 2), 10);
+        assert_eq!(ilog(u64::MAX, 2), 63);
+        assert_eq!(ilog(u64::MAX, 10), 19);
+        assert_eq!(ilog(u64::MAX, u64::MAX), 1);
+        assert_eq!(ilog(u64::MAX - 1, u64::MAX), 0);
+        assert_eq!(ilog(12_157_665_459_056_928_801, 3), 40);
+        assert_eq!(ilog(12_157_665_459_056_928_800, 3), 39);
+
+        for base in 2..40 {
+            for n in 1..5_000 {
+                assert_eq!(ilog(n, base), n.ilog(base));
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_ilog_zero_panic() {
+        ilog(0, 10);
+    }
+
+#[test]
+#[should_panic]
+    fn t_ilog_base_panic() {
+        ilog(10, 1);
+    }
+
+#[test]
+    fn t_factor_fermat() {
+        assert_eq!(factor_fermat(0), None);
+        assert_eq!(factor_fermat(1), None);
+        assert_eq!(factor_fermat(2), None);
+        assert_eq!(factor_fermat(3), None);
+        assert_eq!(factor_fermat(4), Some((2, 2)));
+        assert_eq!(factor_fermat(6), Some((2, 3)));
+        assert_eq!(factor_fermat(1024), Some((2, 512)));
+        assert_eq!(factor_fermat(9), Some((3, 3)));
+        assert_eq!(factor_fermat(15), Some((3, 5)));
+        assert_eq!(factor_fermat(5959), Some((59, 101)));
+        assert_eq!(factor_fermat(97), None);
+        assert_eq!(factor_fermat(1_000_003 * 1_000_003),
+                   Some((1_000_003, 1_000_003)));
+        assert_eq!(factor_fermat(4_294_967_291 * 4_294_967_279),
+                   Some((4_294_967_279, 4_294_967_291)));
+
+        // factors too far apart to be found within the iteration limit
+        assert_eq!(factor_fermat(3 * 4_294_967_291), None);
+
+        for n in 4..2_000 {
+            if let Some((p, q)) = factor_fermat(n) {
+                assert!(p > 1 && p <= q);
+                assert_eq!(p * q, n);
+            } else {
+                assert!(is_prime(n));
+            }
+        }
+    }
+
+#[test]
+    fn t_pollard_p_minus_1() {
+        assert_eq!(pollard_p_minus_1(0, 100), None);
+        assert_eq!(pollard_p_minus_1(3, 100), None);
+        assert_eq!(pollard_p_minus_1(4, 100), Some(2));
+        assert_eq!(pollard_p_minus_1(97, 100), None);
+        assert_eq!(pollard_p_minus_1(1_000_000_007, 1_000), None);
+
+        // 2^16 + 1 is prime, and 65,536 is very smooth
+        assert_eq!(pollard_p_minus_1(65_537 * 1_000_000_007, 2), 
+                   Some(65_537));
+
+        // 2^31 - 2 = 2 * 3^2 * 7 * 11 * 31 * 151 * 331
+        let n = 2_147_483_647 * 4_294_967_291;
+        assert_eq!(pollard_p_minus_1(n, 331), Some(2_147_483_647));
+
+        // neither factor is smooth
+        assert_eq!(pollard_p_minus_1(1_000_000_007 * 4_294_967_291, 1_000), 
+                   None);
+
+        for n in (5..5_000).step_by(2) {
+            if let Some(fac) = pollard_p_minus_1(n, 50) {
+                assert!(fac > 1 && fac < n);
+                assert_eq!(n % fac, 0);
+            }
+        }
+    }
+
+#[test]
+    fn t_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(24), 4);
+        assert_eq!(isqrt(25), 5);
+        assert_eq!(isqrt(4_611_686_014_132_420_608), 2_147_483_646);
+        assert_eq!(isqrt(4_611_686_014_132_420_609), 2_147_483_647);
+        assert_eq!(isqrt(u64::MAX), 4_294_967_295);
     }
 
 #[test]
@@ -530,6 +1929,512 @@ mod tests {
         }
     }
 
+#[test]
+    fn t_verify_factorization() {
+        assert!(verify_factorization(1, &[]));
+        assert!(verify_factorization(2, &[2]));
+        assert!(verify_factorization(12, &[2, 2, 3]));
+        assert!(verify_factorization(12, &[3, 2, 2]));
+        assert!(verify_factorization(u64::MAX,
+                                     &[3, 5, 17, 257, 641, 65_537, 6_700_417]));
+        assert!(verify_factorization(18_446_744_073_709_551_557,
+                                     &[18_446_744_073_709_551_557]));
+        assert!(!verify_factorization(4_294_967_291 * 4_294_967_279,
+                                      &[4_294_967_291 * 4_294_967_279]));
+
+        assert!(!verify_factorization(0, &[]));
+        assert!(!verify_factorization(0, &[2, 3]));
+        assert!(!verify_factorization(1, &[1]));
+        assert!(!verify_factorization(2, &[]));
+        assert!(!verify_factorization(12, &[2, 6]));
+        assert!(!verify_factorization(12, &[2, 2, 3, 1]));
+        assert!(!verify_factorization(12, &[2, 3]));
+        assert!(!verify_factorization(12, &[2, 2, 2, 3]));
+
+        // 2^64 wraps to zero without checked multiplication
+        assert!(!verify_factorization(0, &[2; 64]));
+        assert!(!verify_factorization(u64::MAX,
+                                      &[3, 5, 17, 257, 641, 65_537, 6_700_417,
+                                        2]));
+
+        let factorizer = Factorizer::new();
+        for val in [97020, 982357223, 1302131490435579, 90977992317385808] {
+            let mut factors = factorizer.factorize(val);
+            assert!(verify_factorization(val, &factors));
+
+            factors[0] += 1;
+            assert!(!verify_factorization(val, &factors));
+        }
+    }
+
+#[test]
+    fn t_quick_factorize_cb() {
+        let test_vals = vec![0, 1, 97, 65_535, 65_536, 97020, 982357223,
+                             1302131490435579,
+                             90977992317385808,
+                             (2f64.powf(63.0)) as u64 - 1];
+
+        for val in test_vals {
+            let mut found: Vec<u64> = Vec::new();
+            let factors = quick_factorize_cb(val, |f| found.push(f));
+
+            assert_eq!(factors, quick_factorize(val));
+            found.sort();
+            assert_eq!(found, factors);
+        }
+    }
+
+#[test]
+    fn t_quick_factorize_with_bound() {
+        let factorizer = Factorizer::new();
+        for bound in [0, 1, 2, 3, 100, 65_536, 1_000_000] {
+            for val in 0..300 {
+                assert_eq!(quick_factorize_with_bound(val, bound),
+                           factorizer.factorize(val));
+            }
+
+            let test_vals = [65_535, 65_536, 65_537, 97020, 982357223,
+                             999_985_999_949, 72314573234,
+                             1302131490435579,
+                             90977992317385808,
+                             9_223_372_036_854_775_807];
+            for val in test_vals.iter() {
+                assert_eq!(quick_factorize_with_bound(*val, bound),
+                           factorizer.factorize(*val));
+            }
+        }
+    }
+
+#[test]
+    fn t_factorize_report() {
+        let report = factorize_report(0);
+        assert_eq!(report, FactorReport { factors: vec![], 
+                                          trial_division: 0, rho: 0 });
+
+        for val in [1, 97, 360, 65_535] {
+            let report = factorize_report(val);
+            assert_eq!(report.factors, quick_factorize(val));
+            assert_eq!(report.trial_division, report.factors.len());
+            assert_eq!(report.rho, 0);
+        }
+
+        let report = factorize_report(10_000_019_070_000_133);
+        assert_eq!(report.factors, vec![10_000_019, 1_000_000_007]);
+        assert_eq!(report.trial_division, 0);
+        assert_eq!(report.rho, 2);
+
+        let report = factorize_report(1_048_576_007_340_032);
+        assert_eq!(report.factors.len(), 21);
+        assert_eq!(report.trial_division, 20);
+        assert_eq!(report.rho, 1);
+
+        let test_vals = [65_536, 97020, 982357223, 1302131490435579,
+                         90977992317385808, 9_223_372_036_854_775_807];
+        for val in test_vals.iter() {
+            let report = factorize_report(*val);
+            assert_eq!(report.factors, quick_factorize(*val));
+            assert_eq!(report.trial_division + report.rho, 
+                       report.factors.len());
+        }
+    }
+
+#[test]
+    fn t_factorizer() {
+        let factorizer = Factorizer::new();
+        let sprimes = prime::prime_sieve(MAX_SMALL_NUM);
+        for val in 0..20_000 {
+            assert_eq!(factorizer.factorize(val), 
+                       quick_factorize_wsp(val, &sprimes));
+        }
+        for val in 0..500 {
+            assert_eq!(factorizer.factorize(val), quick_factorize(val));
+        }
+
+        let test_vals = [65_535, 65_536, 65_537, 97020, 982357223,
+                         72314573234,
+                         1302131490435579,
+                         90977992317385808,
+                         9_223_372_036_854_775_807,
+                         u64::MAX];
+        for val in test_vals.iter() {
+            assert_eq!(factorizer.factorize(*val), quick_factorize(*val));
+        }
+
+        assert_eq!(Factorizer::default().factorize(u64::MAX), 
+                   factorizer.factorize(u64::MAX));
+    }
+
+#[test]
+    fn t_factor_tree() {
+        let leaf = |v| FactorNode { value: v, factor: None, children: vec![] };
+
+        assert_eq!(factor_tree(0), leaf(0));
+        assert_eq!(factor_tree(1), leaf(1));
+        assert_eq!(factor_tree(97), leaf(97));
+        assert_eq!(factor_tree(15), FactorNode { 
+            value: 15, 
+            factor: Some(3), 
+            children: vec![leaf(3), leaf(5)],
+        });
+
+        let test_vals = [0, 1, 2, 4, 97, 65_535, 65_536, 97020, 982357223,
+                         1302131490435579,
+                         90977992317385808,
+                         9_223_372_036_854_775_807];
+        for val in test_vals.iter() {
+            let tree = factor_tree(*val);
+            assert_eq!(tree.value, *val);
+
+            let mut leaves = tree.leaves();
+            leaves.sort();
+            assert_eq!(leaves, quick_factorize(*val));
+        }
+    }
+
+#[test]
+    fn t_factorize_exp() {
+        assert_eq!(factorize_exp(0), vec![]);
+        assert_eq!(factorize_exp(1), vec![]);
+        assert_eq!(factorize_exp(97), vec![(97, 1)]);
+        assert_eq!(factorize_exp(65_536), vec![(2, 16)]);
+        assert_eq!(factorize_exp(97020), 
+                   vec![(2, 2), (3, 2), (5, 1), (7, 2), (11, 1)]);
+        assert_eq!(factorize_exp(9_223_372_036_854_775_807),
+                   vec![(7, 2), (73, 1), (127, 1), (337, 1), 
+                        (92737, 1), (649657, 1)]);
+    }
+
+#[test]
+    fn t_factor_map() {
+        assert!(factor_map(0).is_empty());
+        assert!(factor_map(1).is_empty());
+
+        let map = factor_map(360);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&2], 3);
+        assert_eq!(map[&3], 2);
+        assert_eq!(map[&5], 1);
+        assert_eq!(map.get(&7), None);
+
+        assert_eq!(factor_map(65_536), [(2, 16)].iter().cloned().collect());
+
+        let factorizer = Factorizer::new();
+        for n in 1..500 {
+            let map = factor_map(n);
+            let product: u64 = map.iter().map(|(p, e)| p.pow(*e)).product();
+            assert_eq!(product, n);
+
+            let mut primes = factorizer.factorize(n);
+            primes.dedup();
+            assert_eq!(map.len(), primes.len());
+        }
+    }
+
+#[test]
+    fn t_divisors() {
+        assert_eq!(divisors(0), vec![]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(97), vec![1, 97]);
+        assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        assert_eq!(divisors(u64::MAX).len(), 128);
+
+        for n in 1..500 {
+            let brute: Vec<u64> = (1..(n + 1)).filter(|d| n % d == 0).collect();
+            assert_eq!(divisors(n), brute);
+        }
+    }
+
+#[test]
+    fn t_count_divisors_where() {
+        assert_eq!(count_divisors_where(0, |_| true), 0);
+        assert_eq!(count_divisors_where(1, |_| true), 1);
+        assert_eq!(count_divisors_where(12, |d| d % 2 == 1), 2);
+        assert_eq!(count_divisors_where(12, |d| d % 2 == 0), 4);
+        assert_eq!(count_divisors_where(36, perfect_square), 4);
+        assert_eq!(count_divisors_where(36, |_| false), 0);
+        assert_eq!(count_divisors_where(u64::MAX, |_| true), 128);
+        assert_eq!(count_divisors_where(1 << 63, |_| true), 64);
+
+        for n in 1..500 {
+            let divs = divisors(n);
+            assert_eq!(count_divisors_where(n, |_| true), divs.len() as u64);
+            assert_eq!(count_divisors_where(n, |d| d % 3 == 0),
+                       divs.iter().filter(|d| *d % 3 == 0).count() as u64);
+            assert_eq!(count_divisors_where(n, |d| d * d <= n),
+                       divs.iter().filter(|d| *d * *d <= n).count() as u64);
+        }
+    }
+
+#[test]
+    fn t_mobius_sieve() {
+        assert_eq!(mobius_sieve(0), vec![0]);
+        assert_eq!(mobius_sieve(1), vec![0, 1]);
+        assert_eq!(mobius_sieve(10), vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+
+        let mobius = mobius_sieve(5_000);
+        for (n, factors) in factorizations(5_000) {
+            let expected = if factors.iter().any(|f| f.1 > 1) {
+                0
+            } else if factors.len() % 2 == 0 {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(mobius[n as usize], expected);
+        }
+    }
+
+#[test]
+    fn t_mertens() {
+        assert_eq!(mertens(0), 0);
+        assert_eq!(mertens(1), 1);
+        assert_eq!(mertens(2), 0);
+        assert_eq!(mertens(3), -1);
+        assert_eq!(mertens(10), -1);
+        assert_eq!(mertens(100), 1);
+        assert_eq!(mertens(1_000), 2);
+        assert_eq!(mertens(1_000_000), 212);
+    }
+
+#[test]
+    fn t_liouville() {
+        assert_eq!(liouville(1), 1);
+        assert_eq!(liouville(2), -1);
+        assert_eq!(liouville(4), 1);
+        assert_eq!(liouville(12), -1);
+        assert_eq!(liouville(1 << 63), -1);
+        assert_eq!(liouville(u64::MAX), -1);
+
+        assert_eq!(liouville_summatory(0), 0);
+        assert_eq!(liouville_summatory(1), 1);
+        assert_eq!(liouville_summatory(2), 0);
+        assert_eq!(liouville_summatory(10), 0);
+        assert_eq!(liouville_summatory(100), -2);
+        assert_eq!(liouville_summatory(1_000), -14);
+        assert_eq!(liouville_summatory(1_000_000), -530);
+
+        let mut sum = 0;
+        for (n, factors) in factorizations(2_000) {
+            let big_omega: u32 = factors.iter().map(|f| f.1).sum();
+            let expected = if big_omega & 0x01 == 0 { 1 } else { -1 };
+            sum += expected;
+            assert_eq!(liouville_summatory(n), sum + 1);
+        }
+
+        for n in 1..200 {
+            let sum: i64 = divisors(n).iter().map(|d| liouville(*d) as i64)
+                                             .sum();
+            assert_eq!(sum, if perfect_square(n) { 1 } else { 0 });
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_liouville_panic() {
+        liouville(0);
+    }
+
+#[test]
+    fn t_is_smooth() {
+        assert_eq!(is_smooth(0, 100), false);
+        assert_eq!(is_smooth(1, 0), true);
+        assert_eq!(is_smooth(1, 1), true);
+        assert_eq!(is_smooth(1, 2), true);
+        assert_eq!(is_smooth(2, 1), false);
+        assert_eq!(is_smooth(720, 5), true);
+        assert_eq!(is_smooth(720, 4), false);
+        assert_eq!(is_smooth(14, 3), false);
+        assert_eq!(is_smooth(14, 7), true);
+        assert_eq!(is_smooth(1 << 63, 2), true);
+        assert_eq!(is_smooth(18_446_744_073_709_551_557, 1_000_000), false);
+        assert_eq!(is_smooth(1_000_000_007, u64::MAX), true);
+        assert_eq!(is_smooth(2_125_764_000, 5), true);
+        assert_eq!(is_smooth(2_125_764_000 * 7, 5), false);
+
+        let hamming = hamming_numbers(100);
+        let smooth: Vec<u64> = (1..(hamming[99] + 1)).filter(|n| is_smooth(*n, 5))
+                                                     .collect();
+        assert_eq!(smooth, hamming);
+
+        let factorizer = Factorizer::new();
+        for n in 1..3_000 {
+            let largest = factorizer.factorize(n).last().cloned().unwrap_or(1);
+            for bound in [1, 2, 3, 10, 50, 1_000] {
+                assert_eq!(is_smooth(n, bound), largest <= bound);
+            }
+        }
+    }
+
+#[test]
+    fn t_hamming_numbers() {
+        assert_eq!(hamming_numbers(0), vec![]);
+        assert_eq!(hamming_numbers(1), vec![1]);
+        assert_eq!(hamming_numbers(10), vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12]);
+        assert_eq!(hamming_numbers(1691)[1690], 2_125_764_000);
+
+        let hamming = hamming_numbers(13_282);
+        assert!(hamming.windows(2).all(|w| w[0] < w[1]));
+        let factorizer = Factorizer::new();
+        for n in &hamming {
+            let mut factors = factorizer.factorize(*n);
+            factors.dedup();
+            let radical: u64 = factors.iter().product();
+            assert_eq!(30 % radical, 0);
+        }
+
+        let brute: Vec<u64> = (1..10_000).filter(|n| {
+            let mut n = *n;
+            for p in [2, 3, 5] {
+                while n % p == 0 {
+                    n /= p;
+                }
+            }
+            n == 1
+        }).collect();
+        assert_eq!(hamming_numbers(brute.len()), brute);
+    }
+
+#[test]
+#[should_panic]
+    fn t_hamming_numbers_panic() {
+        hamming_numbers(13_283);
+    }
+
+#[test]
+    fn t_multiplicative_partitions() {
+        fn brute(n: u64, max: u64) -> u64 {
+            if n == 1 {
+                return 1;
+            }
+            (2..(max.min(n) + 1)).filter(|d| n % d == 0)
+                                 .map(|d| brute(n / d, d))
+                                 .sum()
+        }
+
+        assert_eq!(multiplicative_partitions(1), 1);
+        assert_eq!(multiplicative_partitions(2), 1);
+        assert_eq!(multiplicative_partitions(12), 4);
+        assert_eq!(multiplicative_partitions(16), 5);
+        assert_eq!(multiplicative_partitions(24), 7);
+        assert_eq!(multiplicative_partitions(36), 9);
+        assert_eq!(multiplicative_partitions(1_000_000_007), 1);
+
+        for n in 1..500 {
+            assert_eq!(multiplicative_partitions(n), brute(n, n));
+        }
+
+        // prime powers give the partition function of the exponent
+        assert_eq!(multiplicative_partitions(1 << 10), 42);
+        assert_eq!(multiplicative_partitions(1 << 63), 
+                   ::partition::part(63));
+
+        // squarefree values give the Bell numbers
+        assert_eq!(multiplicative_partitions(30), 5);
+        assert_eq!(multiplicative_partitions(2_310), 52);
+        assert_eq!(multiplicative_partitions(6_469_693_230), 115_975);
+        assert_eq!(multiplicative_partitions(u64::MAX), 877);
+    }
+
+#[test]
+#[should_panic]
+    fn t_multiplicative_partitions_panic() {
+        multiplicative_partitions(0);
+    }
+
+#[test]
+    fn t_sum_of_two_squares() {
+        assert_eq!(sum_of_two_squares(0), vec![(0, 0)]);
+        assert_eq!(sum_of_two_squares(1), vec![(0, 1)]);
+        assert_eq!(sum_of_two_squares(2), vec![(1, 1)]);
+        assert_eq!(sum_of_two_squares(3), vec![]);
+        assert_eq!(sum_of_two_squares(25), vec![(0, 5), (3, 4)]);
+        assert_eq!(sum_of_two_squares(50), vec![(1, 7), (5, 5)]);
+        assert_eq!(sum_of_two_squares(21), vec![]);
+        assert_eq!(sum_of_two_squares(325), vec![(1, 18), (6, 17), (10, 15)]);
+        assert_eq!(sum_of_two_squares(1 << 40), vec![(0, 1 << 20)]);
+        assert_eq!(sum_of_two_squares(1 << 41), vec![(1 << 20, 1 << 20)]);
+        assert_eq!(sum_of_two_squares(1_000_000_007), vec![]);
+        assert_eq!(sum_of_two_squares(1_000_000_009), vec![(3_747, 31_400)]);
+
+        for n in 0..2_000 {
+            let mut brute: Vec<(u64, u64)> = Vec::new();
+            for a in 0..45 {
+                for b in a..45 {
+                    if a * a + b * b == n {
+                        brute.push((a, b));
+                    }
+                }
+            }
+            assert_eq!(sum_of_two_squares(n), brute);
+        }
+    }
+
+#[test]
+    fn t_count_sum_of_two_squares() {
+        assert_eq!(count_sum_of_two_squares(0), 1);
+        assert_eq!(count_sum_of_two_squares(1), 4);
+        assert_eq!(count_sum_of_two_squares(2), 4);
+        assert_eq!(count_sum_of_two_squares(3), 0);
+        assert_eq!(count_sum_of_two_squares(5), 8);
+        assert_eq!(count_sum_of_two_squares(9), 4);
+        assert_eq!(count_sum_of_two_squares(25), 12);
+        assert_eq!(count_sum_of_two_squares(325), 24);
+        assert_eq!(count_sum_of_two_squares(1_000_000_007), 0);
+        assert_eq!(count_sum_of_two_squares(1_000_000_009), 8);
+        assert_eq!(count_sum_of_two_squares(1 << 63), 4);
+        assert_eq!(count_sum_of_two_squares(u64::MAX), 0);
+
+        // each pair with a <= b stands for up to eight signed and
+        // ordered representations
+        for n in 0..500 {
+            let count: u64 = sum_of_two_squares(n).iter().map(|&(a, b)| {
+                let signs = if a == 0 { 1 } else { 2 }
+                          * if b == 0 { 1 } else { 2 };
+                if a == b { signs } else { signs * 2 }
+            }).sum();
+            assert_eq!(count_sum_of_two_squares(n), count);
+        }
+    }
+
+#[test]
+    fn t_factorizations() {
+        assert_eq!(factorizations(0).count(), 0);
+        assert_eq!(factorizations(1).count(), 0);
+        assert_eq!(factorizations(2).collect::<Vec<_>>(), 
+                   vec![(2, vec![(2, 1)])]);
+
+        let max = 100_000;
+        let mut expected = 2;
+        for (n, factors) in factorizations(max) {
+            assert_eq!(n, expected);
+            expected += 1;
+
+            if n < 1_000 || n % 997 == 0 || n > max - 100 {
+                assert_eq!(factors, factorize_exp(n));
+            }
+        }
+        assert_eq!(expected, max + 1);
+
+        let spf = spf_sieve(30);
+        assert_eq!(spf[..10], [0, 0, 2, 3, 2, 5, 2, 7, 2, 3]);
+        assert_eq!(spf[25], 5);
+        assert_eq!(spf[29], 29);
+    }
+
+#[test]
+#[cfg(feature = "serde")]
+    fn t_factorize_exp_serde() {
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(&factorize_exp(360), &[
+            Token::Seq { len: Some(3) },
+            Token::Tuple { len: 2 }, Token::U64(2), Token::U32(3), Token::TupleEnd,
+            Token::Tuple { len: 2 }, Token::U64(3), Token::U32(2), Token::TupleEnd,
+            Token::Tuple { len: 2 }, Token::U64(5), Token::U32(1), Token::TupleEnd,
+            Token::SeqEnd,
+        ]);
+    }
+
 #[test]
 #[ignore]
     fn t_quick_factorize_long() {
@@ -27,6 +27,143 @@ pub fn figurate(s: i64, n: i64) -> i64 {
     ((s - 2) * n * (n - 1) / 2) + n
 }
 
+/// Return the `n`th figurate number with `s` sides, or `None` if
+/// it is too large for an `i64`.
+///
+/// This works in the same way as `figurate()`, but computes the value
+/// with `i128`s, so it never overflows silently.
+///
+/// # Panics
+///
+/// Panics if `s` is less than three
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_figurate;
+/// assert_eq!(checked_figurate(45, 91), Some(176176));
+/// assert_eq!(checked_figurate(3, 5_000_000_000), None);
+/// ```
+pub fn checked_figurate(s: i64, n: i64) -> Option<i64> {
+    assert!(s >= 3, "cannot generate figurate numbers with less than
+                         three sides!");
+
+    match exact_figurate(s, n) {
+        Some(val) if val >= i64::MIN as i128 && val <= i64::MAX as i128 => {
+            Some(val as i64)
+        },
+        _ => None,
+    }
+}
+
+/// Return the index of `x` in the sequence of figurate numbers
+/// with `s` sides, that is, `Some(n)` if `x == figurate(s, n)`
+/// for some `n >= 0`, and `None` otherwise.
+///
+/// This is the inverse of `figurate()`. Rather than solving the
+/// quadratic with floating point arithmetic, the index is found with
+/// a binary search over exact integer values, so the result is correct
+/// for any `x`.
+///
+/// # Panics
+///
+/// Panics if `s` is less than three
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::polygonal_index;
+/// assert_eq!(polygonal_index(3, 55), Some(10));
+/// assert_eq!(polygonal_index(5, 35), Some(5));
+/// assert_eq!(polygonal_index(3, 56), None);
+/// ```
+pub fn polygonal_index(s: i64, x: i64) -> Option<i64> {
+    assert!(s >= 3, "cannot generate figurate numbers with less than
+                         three sides!");
+
+    if x < 0 {
+        return None;
+    }
+
+    // figurate(s, n) >= n for n >= 0, so the index is at most x
+    let mut lo: i64 = 0;
+    let mut hi: i64 = x;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match exact_figurate(s, mid) {
+            Some(val) if val < x as i128 => lo = mid + 1,
+            _ => hi = mid,
+        }
+    }
+
+    if exact_figurate(s, lo) == Some(x as i128) {
+        Some(lo)
+    } else {
+        None
+    }
+}
+
+/// Return the first `count` positive numbers that are both `s1`-gonal
+/// and `s2`-gonal figurate numbers, in increasing order.
+///
+/// This works by walking both sequences of figurate numbers at once,
+/// always advancing the smaller of the two, and recording the values
+/// found in both. If the sequences pass `i64::MAX` before `count` values
+/// are found, the values found so far are returned.
+///
+/// Note that the number of steps taken grows with the square root of
+/// the largest value found, so asking for more values than exist below
+/// `i64::MAX` can take a very long time.
+///
+/// # Panics
+///
+/// Panics if `s1` or `s2` is less than three
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::figurate_overlap;
+/// assert_eq!(figurate_overlap(3, 4, 4), vec![1, 36, 1225, 41616]);
+/// ```
+pub fn figurate_overlap(s1: i64, s2: i64, count: usize) -> Vec<i64> {
+    assert!(s1 >= 3 && s2 >= 3, "cannot generate figurate numbers with less 
+                                 than three sides!");
+
+    let mut overlap: Vec<i64> = Vec::new();
+
+    let (mut n1, mut n2) = (1, 1);
+    while overlap.len() < count {
+        let a = match exact_figurate(s1, n1) {
+            Some(val) if val <= i64::MAX as i128 => val,
+            _ => break,
+        };
+        let b = match exact_figurate(s2, n2) {
+            Some(val) if val <= i64::MAX as i128 => val,
+            _ => break,
+        };
+
+        if a == b {
+            overlap.push(a as i64);
+        }
+
+        if a <= b {
+            n1 += 1;
+        } 
+        if b <= a {
+            n2 += 1;
+        }
+    }
+
+    overlap
+}
+
+// helper function to compute figurate(s, n) without overflowing,
+// returning None if the value is too large for an i128
+fn exact_figurate(s: i64, n: i64) -> Option<i128> {
+    let (s, n) = (s as i128, n as i128);
+    (n * (n - 1) / 2).checked_mul(s - 2)?.checked_add(n)
+}
+
 /// Return the `n`th general figurate number with `s` sides
 ///
 /// # Panics
@@ -51,6 +188,30 @@ pub fn general_figurate(s: i64, n: i64) -> i64 {
     figurate(s, n / 2)
 }
 
+/// Return the `n`th general figurate number with `s` sides, or `None`
+/// if it is too large for an `i64`.
+///
+/// This works in the same way as `general_figurate()`, but uses
+/// `checked_figurate()` to compute the value.
+///
+/// # Panics
+///
+/// Panics if `s` is less than three
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_general_figurate;
+/// assert_eq!(checked_general_figurate(5, 5), Some(12));
+/// assert_eq!(checked_general_figurate(5, i64::MAX), None);
+/// ```
+pub fn checked_general_figurate(s: i64, n: i64) -> Option<i64> {
+    let n = n as i128 + 1;
+    let n = if n % 2 == 1 { -n / 2 } else { n / 2 };
+
+    checked_figurate(s, n as i64)
+}
+
 /// Return the `n`th centered figurate number with `s` sides
 ///
 /// # Panics
@@ -123,6 +284,68 @@ pub fn hexagonal_number(n: i64) -> i64 { figurate(6, n) }
 /// ```
 pub fn general_pentagonal_number(n: i64) -> i64 { general_figurate(5, n) }
 
+/// Return the `n`th triangular number, or `None` if it is too large
+/// for an `i64`
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_triangular_number;
+/// assert_eq!(checked_triangular_number(5), Some(15));
+/// assert_eq!(checked_triangular_number(4_294_967_296), None);
+/// ```
+pub fn checked_triangular_number(n: i64) -> Option<i64> { checked_figurate(3, n) }
+
+/// Return the `n`th square number, or `None` if it is too large
+/// for an `i64`
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_square_number;
+/// assert_eq!(checked_square_number(5), Some(25));
+/// assert_eq!(checked_square_number(3_037_000_500), None);
+/// ```
+pub fn checked_square_number(n: i64) -> Option<i64> { checked_figurate(4, n) }
+
+/// Return the `n`th pentagonal number, or `None` if it is too large
+/// for an `i64`
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_pentagonal_number;
+/// assert_eq!(checked_pentagonal_number(5), Some(35));
+/// assert_eq!(checked_pentagonal_number(2_479_700_525), None);
+/// ```
+pub fn checked_pentagonal_number(n: i64) -> Option<i64> { checked_figurate(5, n) }
+
+/// Return the `n`th hexagonal number, or `None` if it is too large
+/// for an `i64`
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_hexagonal_number;
+/// assert_eq!(checked_hexagonal_number(5), Some(45));
+/// assert_eq!(checked_hexagonal_number(2_147_483_649), None);
+/// ```
+pub fn checked_hexagonal_number(n: i64) -> Option<i64> { checked_figurate(6, n) }
+
+/// Return the `n`th general pentagonal number, or `None` if it is too large
+/// for an `i64`
+///
+/// # Examples
+///
+/// ```
+/// use reikna::figurate::checked_general_pentagonal_number;
+/// assert_eq!(checked_general_pentagonal_number(5), Some(12));
+/// assert_eq!(checked_general_pentagonal_number(i64::MAX), None);
+/// ```
+pub fn checked_general_pentagonal_number(n: i64) -> Option<i64> {
+    checked_general_figurate(5, n)
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -157,6 +380,129 @@ mod tests {
         assert_eq!(figurate(9, 10), 325);
     }
 
+#[test]
+    fn t_checked_figurate() {
+        for s in 3..20 {
+            for n in -100..100 {
+                assert_eq!(checked_figurate(s, n), Some(figurate(s, n)));
+                assert_eq!(checked_general_figurate(s, n), 
+                           Some(general_figurate(s, n)));
+            }
+        }
+
+        // the largest index of each kind that fits in an i64
+        let limits = [(3, 4_294_967_295), (4, 3_037_000_499),
+                      (5, 2_479_700_524), (6, 2_147_483_648)];
+        for (s, n) in limits {
+            let val = checked_figurate(s, n).unwrap();
+            assert_eq!(val as i128, exact_figurate(s, n).unwrap());
+            assert_eq!(polygonal_index(s, val), Some(n));
+            assert_eq!(checked_figurate(s, n + 1), None);
+        }
+
+        assert_eq!(checked_triangular_number(4_294_967_295), 
+                   Some(9_223_372_034_707_292_160));
+        assert_eq!(checked_triangular_number(4_294_967_296), None);
+        assert_eq!(checked_square_number(3_037_000_499), 
+                   Some(3_037_000_499 * 3_037_000_499));
+        assert_eq!(checked_square_number(3_037_000_500), None);
+        assert_eq!(checked_square_number(-3_037_000_499), 
+                   Some(3_037_000_499 * 3_037_000_499));
+        assert_eq!(checked_pentagonal_number(2_479_700_524), 
+                   checked_figurate(5, 2_479_700_524));
+        assert_eq!(checked_pentagonal_number(2_479_700_525), None);
+        assert_eq!(checked_hexagonal_number(2_147_483_648), 
+                   Some(9_223_372_034_707_292_160));
+        assert_eq!(checked_hexagonal_number(2_147_483_649), None);
+        assert_eq!(checked_figurate(3, -4_294_967_296), 
+                   checked_triangular_number(4_294_967_295));
+        assert_eq!(checked_figurate(3, -4_294_967_297), None);
+        assert_eq!(checked_figurate(3, i64::MAX), None);
+        assert_eq!(checked_figurate(i64::MAX, i64::MAX), None);
+        assert_eq!(checked_figurate(i64::MAX, 2), Some(i64::MAX));
+
+        for n in 0..100 {
+            assert_eq!(checked_triangular_number(n), Some(triangular_number(n)));
+            assert_eq!(checked_square_number(n), Some(square_number(n)));
+            assert_eq!(checked_pentagonal_number(n), Some(pentagonal_number(n)));
+            assert_eq!(checked_hexagonal_number(n), Some(hexagonal_number(n)));
+            assert_eq!(checked_general_pentagonal_number(n), 
+                       Some(general_pentagonal_number(n)));
+        }
+
+        assert_eq!(checked_general_pentagonal_number(i64::MAX), None);
+        assert_eq!(checked_general_pentagonal_number(i64::MIN), None);
+        assert_eq!(checked_general_figurate(3, 8_589_934_589), 
+                   checked_triangular_number(4_294_967_295));
+        assert_eq!(checked_general_figurate(3, 8_589_934_591), None);
+    }
+
+#[test]
+#[should_panic]
+    fn t_checked_figurate_panic() {
+        checked_figurate(2, 1);
+    }
+
+#[test]
+    fn t_polygonal_index() {
+        assert_eq!(polygonal_index(3, 0), Some(0));
+        assert_eq!(polygonal_index(3, 1), Some(1));
+        assert_eq!(polygonal_index(3, 55), Some(10));
+        assert_eq!(polygonal_index(3, 56), None);
+        assert_eq!(polygonal_index(4, 99), None);
+        assert_eq!(polygonal_index(4, 100), Some(10));
+        assert_eq!(polygonal_index(5, 35), Some(5));
+        assert_eq!(polygonal_index(5, 36), None);
+        assert_eq!(polygonal_index(9, -1), None);
+        assert_eq!(polygonal_index(4, 3_037_000_499 * 3_037_000_499), 
+                   Some(3_037_000_499));
+        assert_eq!(polygonal_index(4, i64::MAX), None);
+        assert_eq!(polygonal_index(i64::MAX, i64::MAX), Some(2));
+        assert_eq!(polygonal_index(i64::MAX, i64::MAX - 1), None);
+
+        for s in 3..20 {
+            let mut n = 0;
+            for x in 0..2_000 {
+                if figurate(s, n) == x {
+                    assert_eq!(polygonal_index(s, x), Some(n));
+                    n += 1;
+                } else {
+                    assert_eq!(polygonal_index(s, x), None);
+                }
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_polygonal_index_panic() {
+        polygonal_index(2, 1);
+    }
+
+#[test]
+    fn t_figurate_overlap() {
+        assert_eq!(figurate_overlap(3, 4, 0), vec![]);
+        assert_eq!(figurate_overlap(3, 4, 4), vec![1, 36, 1225, 41616]);
+        assert_eq!(figurate_overlap(4, 3, 4), vec![1, 36, 1225, 41616]);
+        assert_eq!(figurate_overlap(3, 3, 5), vec![1, 3, 6, 10, 15]);
+        assert_eq!(figurate_overlap(3, 6, 5), vec![1, 6, 15, 28, 45]);
+        assert_eq!(figurate_overlap(3, 5, 3), vec![1, 210, 40755]);
+        assert_eq!(figurate_overlap(5, 6, 3), vec![1, 40755, 1533776805]);
+
+        let overlap = figurate_overlap(3, 4, 8);
+        assert_eq!(overlap[7], 55_420_693_056);
+        for x in overlap {
+            assert!(polygonal_index(3, x).is_some());
+            assert!(polygonal_index(4, x).is_some());
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_figurate_overlap_panic() {
+        figurate_overlap(3, 2, 1);
+    }
+
 #[test]
     fn t_general_figurate() {
         assert_eq!(general_figurate(5, 0), 0);
@@ -1,11 +1,17 @@
 //! Module for working with `Function`s
 //!
-//! This module contains a type alias for `Rc<Fn(f64) -> f64>`,
-//! which is used in many other modules, and functions for
-//! working with the alias.
+//! This module contains:
+//!
+//! * a type alias for `Rc<Fn(f64) -> f64>`, which is used in many
+//!   other modules, and its multivariate counterpart `FunctionN`
+//! * functions for working with these aliases
+//! * helpers for comparing the approximate results they produce
 
 pub use std::rc::Rc;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Type alias used to represent functions.
 ///
 /// A `Function` is a `Fn` that takes a single `f64`,
@@ -15,6 +21,15 @@ pub use std::rc::Rc;
 /// and subsequently consumed in other functions.
 pub type Function = Rc<dyn Fn(f64) -> f64>;
 
+/// Type alias used to represent functions of several variables.
+///
+/// A `FunctionN` is a `Fn` that takes a slice of `f64`s, one for
+/// each variable, and returns a single `f64`.
+///
+/// Like `Function`, these are stored in an `Rc` so they can be
+/// `cloned()` and consumed in other functions.
+pub type FunctionN = Rc<dyn Fn(&[f64]) -> f64>;
+
 /// Macro for creating a `Function`.
 ///
 /// More idiomatic than calling `Rc::new()`.
@@ -34,3 +49,170 @@ pub type Function = Rc<dyn Fn(f64) -> f64>;
 macro_rules! func {
     ($e:expr) => (Rc::new($e) as Function);
 }
+
+/// The default tolerance used when comparing approximate results,
+/// such as the estimates of the `derivative` and `integral` modules.
+pub const DEFAULT_TOLERANCE: f64 = 0.001;
+
+/// Return `true` if `a` and `b` are within `tol` of each other.
+///
+/// Values that are exactly equal are always approximately equal,
+/// so infinities of the same sign compare as equal. An infinity is
+/// never approximately equal to any other value, and `NaN` is never
+/// approximately equal to anything, including itself.
+///
+/// `DEFAULT_TOLERANCE` is a reasonable choice of `tol` for comparing
+/// the estimates of the `derivative` and `integral` modules.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::func::*;
+/// assert_eq!(approx_eq(1.0, 1.0005, DEFAULT_TOLERANCE), true);
+/// assert_eq!(approx_eq(1.0, 1.1, DEFAULT_TOLERANCE), false);
+/// ```
+pub fn approx_eq(a: f64, b: f64, tol: f64) -> bool {
+    if a == b {
+        return true;
+    } else if a.is_infinite() || b.is_infinite() {
+        return false;
+    }
+
+    (a - b).abs() <= tol
+}
+
+/// The symmetry of a `Function` about the y-axis, see `symmetry()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Symmetry {
+    /// `f(-x) = f(x)`, for example `x^2` or `cos(x)`.
+    Even,
+    /// `f(-x) = -f(x)`, for example `x^3` or `sin(x)`.
+    Odd,
+    /// Neither even nor odd, for example `x^2 + x`.
+    Neither,
+}
+
+/// Return the `Symmetry` of `f`, found by comparing `f(x)` and 
+/// `f(-x)` at each of the points in `test_points`.
+///
+/// The values are compared with `approx_eq()` and `DEFAULT_TOLERANCE`.
+/// As only a finite set of points is sampled, the result is a guess, 
+/// and a function can only be reported as even or odd if it is 
+/// defined at every point and its negation.
+///
+/// A function that is both even and odd, such as `f(x) = 0`,
+/// is reported as `Symmetry::Even`.
+///
+/// # Panics
+///
+/// Panics if `test_points` is empty.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::func::*;
+/// let points = [0.5, 1.0, 2.0, 3.0];
+/// assert_eq!(symmetry(&func!(|x: f64| x * x), &points), Symmetry::Even);
+/// assert_eq!(symmetry(&func!(|x: f64| x.sin()), &points), Symmetry::Odd);
+/// assert_eq!(symmetry(&func!(|x: f64| x.exp()), &points), 
+///            Symmetry::Neither);
+/// # }
+/// ```
+pub fn symmetry(f: &Function, test_points: &[f64]) -> Symmetry {
+    assert!(!test_points.is_empty(), "no points to test symmetry at!");
+
+    let mut even = true;
+    let mut odd = true;
+    for x in test_points {
+        let pos = f(*x);
+        let neg = f(-*x);
+        even = even && approx_eq(neg, pos, DEFAULT_TOLERANCE);
+        odd = odd && approx_eq(neg, -pos, DEFAULT_TOLERANCE);
+    }
+
+    if even {
+        Symmetry::Even
+    } else if odd {
+        Symmetry::Odd
+    } else {
+        Symmetry::Neither
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_approx_eq() {
+        assert_eq!(approx_eq(0.0, 0.0, 0.0), true);
+        assert_eq!(approx_eq(0.0, -0.0, 0.0), true);
+        assert_eq!(approx_eq(1.5, 1.5, 0.0), true);
+        assert_eq!(approx_eq(0.1 + 0.2, 0.3, 0.0), false);
+        assert_eq!(approx_eq(0.1 + 0.2, 0.3, f64::EPSILON), true);
+
+        assert_eq!(approx_eq(1.0, 1.0005, DEFAULT_TOLERANCE), true);
+        assert_eq!(approx_eq(1.0005, 1.0, DEFAULT_TOLERANCE), true);
+        assert_eq!(approx_eq(-1.0, -1.001, DEFAULT_TOLERANCE), true);
+        assert_eq!(approx_eq(1.0, 1.1, DEFAULT_TOLERANCE), false);
+        assert_eq!(approx_eq(1.0, -1.0, 1.0), false);
+        assert_eq!(approx_eq(1.0, -1.0, 2.0), true);
+
+        assert_eq!(approx_eq(f64::NAN, f64::NAN, f64::INFINITY), false);
+        assert_eq!(approx_eq(f64::NAN, 1.0, DEFAULT_TOLERANCE), false);
+        assert_eq!(approx_eq(1.0, f64::NAN, DEFAULT_TOLERANCE), false);
+        assert_eq!(approx_eq(1.0, 1.0, f64::NAN), true);
+        assert_eq!(approx_eq(1.0, 1.0005, f64::NAN), false);
+
+        assert_eq!(approx_eq(f64::INFINITY, f64::INFINITY, 0.0), true);
+        assert_eq!(approx_eq(f64::NEG_INFINITY, f64::NEG_INFINITY, 0.0), true);
+        assert_eq!(approx_eq(f64::INFINITY, f64::NEG_INFINITY,
+                             f64::INFINITY), false);
+        assert_eq!(approx_eq(f64::INFINITY, f64::MAX, f64::MAX), false);
+        assert_eq!(approx_eq(1.0, 2.0, f64::INFINITY), true);
+
+        let f: Function = func!(|x: f64| x * x);
+        assert!(approx_eq(f(0.1), 0.01, 1.0e-15));
+    }
+
+#[test]
+    fn t_symmetry() {
+        let points = [0.0, 0.25, 0.5, 1.0, 2.0, 3.5, 10.0];
+
+        assert_eq!(symmetry(&func!(|x: f64| x * x), &points), Symmetry::Even);
+        assert_eq!(symmetry(&func!(|x: f64| x.cos()), &points), 
+                   Symmetry::Even);
+        assert_eq!(symmetry(&func!(|x: f64| x.abs()), &points), 
+                   Symmetry::Even);
+        assert_eq!(symmetry(&func!(|_| 0.0), &points), Symmetry::Even);
+
+        assert_eq!(symmetry(&func!(|x: f64| x * x * x), &points), 
+                   Symmetry::Odd);
+        assert_eq!(symmetry(&func!(|x: f64| x.sin()), &points), 
+                   Symmetry::Odd);
+        assert_eq!(symmetry(&func!(|x: f64| x.powi(5) - 3.0 * x), &points), 
+                   Symmetry::Odd);
+
+        assert_eq!(symmetry(&func!(|x: f64| x * x + x), &points), 
+                   Symmetry::Neither);
+        assert_eq!(symmetry(&func!(|x: f64| x.exp()), &points), 
+                   Symmetry::Neither);
+        assert_eq!(symmetry(&func!(|x: f64| x * x * x + 1.0), &points), 
+                   Symmetry::Neither);
+        assert_eq!(symmetry(&func!(|x: f64| x.sqrt()), &points), 
+                   Symmetry::Neither);
+
+        // every function looks even when sampled only at zero
+        assert_eq!(symmetry(&func!(|x: f64| x * x + x), &[0.0]), 
+                   Symmetry::Even);
+    }
+
+#[test]
+#[should_panic]
+    fn t_symmetry_panic() {
+        symmetry(&func!(|x: f64| x), &[]);
+    }
+}
@@ -1,7 +1,8 @@
 //! Module for working with integrals.
 //!
 //! This module has functions for estimating the values of
-//! integrals through numeric integration techniques.
+//! integrals through numeric integration techniques, including
+//! Monte Carlo sampling.
 
 pub use super::func::*;
 
@@ -58,6 +59,107 @@ pub fn integrate_wp(f: &Function, a: f64, b: f64, p: u64) -> f64 {
     integral * delta / 3.0
 }
 
+/// Estimate the value of the integral of `f` over `[a, b]` using a
+/// left Riemann sum with `p` subintervals.
+///
+/// Each subinterval is approximated by a rectangle with the height of
+/// `f` at its left edge. This is much less accurate than Simpson's rule,
+/// used by `integrate_wp()`, and is mostly useful for comparison. When
+/// `f` is increasing over `[a, b]` the result is an underestimate, and
+/// when `f` is decreasing the result is an overestimate.
+///
+/// If `a` is equal to `b` or `p` equals zero, `zero` will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::integral::*;
+/// 
+/// let f = func!(|x| x);
+/// assert_eq!(left_riemann(&f, 0.0, 1.0, 4), 0.375);
+///# }
+/// ```
+pub fn left_riemann(f: &Function, a: f64, b: f64, p: u64) -> f64 {
+    riemann_sum(f, a, b, p, 0)
+}
+
+/// Estimate the value of the integral of `f` over `[a, b]` using a
+/// right Riemann sum with `p` subintervals.
+///
+/// This function works in the same way as `left_riemann()`, but uses
+/// the height of `f` at the right edge of each subinterval. When `f`
+/// is increasing over `[a, b]` the result is an overestimate, and when
+/// `f` is decreasing the result is an underestimate.
+///
+/// If `a` is equal to `b` or `p` equals zero, `zero` will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::integral::*;
+/// 
+/// let f = func!(|x| x);
+/// assert_eq!(right_riemann(&f, 0.0, 1.0, 4), 0.625);
+///# }
+/// ```
+pub fn right_riemann(f: &Function, a: f64, b: f64, p: u64) -> f64 {
+    riemann_sum(f, a, b, p, 1)
+}
+
+// helper function to compute a Riemann sum, sampling each subinterval
+// at its left edge when `offset` is 0 and its right edge when it is 1
+fn riemann_sum(f: &Function, a: f64, b: f64, p: u64, offset: u64) -> f64 {
+    if (a - b).abs() < f64::EPSILON || p == 0 {
+        return 0.0;
+    }
+
+    let delta = (b - a) / p as f64;
+    let sum: f64 = (0..p).map(|i| f(a + (i + offset) as f64 * delta))
+                         .sum();
+
+    sum * delta
+}
+
+/// Estimate the value of the integral of `f` over `[a, b]`, along
+/// with an estimate of the error of the result.
+///
+/// This function calls `integrate_wp()` with both `p` and `2 * p`
+/// subintervals, returning a tuple of the `2 * p` estimate and the
+/// error estimate
+///
+/// ``` text
+/// error = |I(2p) - I(p)| / 15
+/// ```
+///
+/// which is the Richardson estimate of the error in Simpson's rule.
+/// If the error is too large, the integral can be recomputed with a 
+/// larger `p`. For the estimate to be accurate, `p` should be even.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::integral::*;
+/// 
+/// let f = func!(|x: f64| x.powi(4));
+/// let (integral, error) = integrate_with_error(&f, 0.0, 1.0, 8);
+/// assert!((integral - 0.2).abs() <= error);
+///# }
+/// ```
+pub fn integrate_with_error(f: &Function, a: f64, b: f64, p: u64) -> (f64, f64) {
+    let coarse = integrate_wp(f, a, b, p);
+    let fine = integrate_wp(f, a, b, p * 2);
+
+    (fine, (fine - coarse).abs() / 15.0)
+}
+
 /// Estimate the value of the integral of `f` over `[a, b]`.
 ///
 /// This is a helper function that calls `integrate_wp()` using
@@ -97,6 +199,105 @@ pub fn integrate(f: &Function, a: f64, b: f64) -> f64 {
     integrate_wp(f, a, b, p)
 }
 
+/// Estimate the value of the integral of `f` over `[a, b]` using
+/// stratified Monte Carlo sampling.
+///
+/// `[a, b]` is divided into `strata` equal subintervals, and `f` is
+/// sampled at `per_stratum` uniformly random points within each one.
+/// Compared to sampling the same number of points uniformly over all
+/// of `[a, b]`, which is the case of a single stratum, this spreads the
+/// samples out evenly, greatly reducing the variance of the estimate.
+///
+/// The random points are generated by a simple pseudo-random number
+/// generator seeded with `seed`, so the same arguments always give the
+/// same estimate.
+///
+/// # Panics
+///
+/// Panics if `strata` or `per_stratum` is zero.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate reikna;
+/// # fn main() {
+/// use reikna::integral::*;
+///
+/// let f = func!(|x: f64| x * x);
+/// let integral = integrate_stratified(&f, 0.0, 3.0, 100, 10, 1);
+/// assert!((integral - 9.0).abs() < 0.01);
+///# }
+/// ```
+pub fn integrate_stratified(f: &Function, a: f64, b: f64, strata: usize,
+                            per_stratum: usize, seed: u64) -> f64 {
+    assert!(strata != 0, "cannot sample zero strata!");
+    assert!(per_stratum != 0, "cannot take zero samples per stratum!");
+
+    let width = (b - a) / strata as f64;
+    let mut state = seed;
+
+    let mut sum = 0.0;
+    for i in 0..strata {
+        let start = a + width * i as f64;
+        for _ in 0..per_stratum {
+            sum += f(start + width * next_random(&mut state));
+        }
+    }
+
+    sum * width / per_stratum as f64
+}
+
+// helper function to advance the splitmix64 generator `state`,
+// returning a pseudo-random value in [0, 1)
+fn next_random(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Return the exact value of the integral of the polynomial with
+/// coefficients `coeffs` over `[a, b]`.
+///
+/// `coeffs[i]` is the coefficient of `x^i`, so the polynomial is
+///
+/// ``` text
+/// coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...
+/// ```
+///
+/// Rather than using numeric integration, each term is integrated
+/// analytically, and the resulting antiderivative is evaluated at `a`
+/// and `b` using Horner's method. The result is exact up to floating
+/// point rounding, even for polynomials of degree four and above, for
+/// which `integrate()` only gives an estimate.
+///
+/// If `coeffs` is empty, zero is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::integral::integrate_polynomial;
+/// // x^2
+/// assert_eq!(integrate_polynomial(&[0.0, 0.0, 1.0], 0.0, 3.0), 9.0);
+///
+/// // x^4 - 2x + 1
+/// let integral = integrate_polynomial(&[1.0, -2.0, 0.0, 0.0, 1.0], 0.0, 1.0);
+/// assert!((integral - 0.2).abs() < 1e-15);
+/// ```
+pub fn integrate_polynomial(coeffs: &[f64], a: f64, b: f64) -> f64 {
+    let antiderivative = |x: f64| {
+        coeffs.iter().enumerate().rev().fold(0.0, |acc, (i, c)| {
+            acc * x + c / (i + 1) as f64
+        }) * x
+    };
+
+    antiderivative(b) - antiderivative(a)
+}
+
 /// Return a `Function` that estimates the `n`th integral of `f`, using a
 /// constant of `c` and a positive precision constant of `p`.
 ///
@@ -241,6 +442,142 @@ mod tests {
         assert_fp!(f_int(-1.0), 1.0 / 12.0);
     }
 
+#[test]
+    fn t_integrate_with_error() {
+        let f = func!(|x: f64| x.powi(4));
+
+        let mut last_error = f64::INFINITY;
+        for p in [2, 4, 8, 16, 32, 64] {
+            let (integral, error) = integrate_with_error(&f, 0.0, 1.0, p);
+            assert!(error < last_error);
+            assert!((integral - 0.2).abs() <= error);
+            last_error = error;
+        }
+
+        let (integral, error) = integrate_with_error(&f, -2.0, 2.0, 1_000);
+        assert_fp!(integral, 64.0 / 5.0);
+        assert!(error < 1e-9);
+
+        let f = func!(|x: f64| x * x);
+        let (integral, error) = integrate_with_error(&f, 0.0, 1.0, 2);
+        assert_fp!(integral, 1.0 / 3.0);
+        assert_fp!(error, 0.0);
+
+        assert_eq!(integrate_with_error(&f, 1.0, 1.0, 10), (0.0, 0.0));
+        assert_eq!(integrate_with_error(&f, 0.0, 1.0, 0), (0.0, 0.0));
+    }
+
+#[test]
+    fn t_integrate_stratified() {
+        let f = func!(|x: f64| (10.0 * x).sin());
+        let exact = (1.0 - 10.0_f64.cos()) / 10.0;
+
+        assert_eq!(integrate_stratified(&f, 0.0, 1.0, 50, 20, 7),
+                   integrate_stratified(&f, 0.0, 1.0, 50, 20, 7));
+        assert_fp!(integrate_stratified(&f, 0.0, 1.0, 50, 20, 7), exact, 1e-2);
+        assert_fp!(integrate_stratified(&f, 1.0, 0.0, 50, 20, 7), -exact, 1e-2);
+        assert_eq!(integrate_stratified(&f, 1.0, 1.0, 50, 20, 7), 0.0);
+
+        // a single stratum is plain Monte Carlo sampling, so for the same
+        // number of samples the stratified estimates should vary far less
+        let variance = |strata: usize, per_stratum: usize| {
+            let estimates: Vec<f64> = (0..100).map(|seed| {
+                integrate_stratified(&f, 0.0, 1.0, strata, per_stratum, seed)
+            }).collect();
+
+            estimates.iter().map(|e| (e - exact) * (e - exact)).sum::<f64>()
+                / estimates.len() as f64
+        };
+
+        let plain = variance(1, 1_000);
+        let stratified = variance(100, 10);
+        assert!(stratified * 100.0 < plain);
+    }
+
+#[test]
+#[should_panic]
+    fn t_integrate_stratified_panic() {
+        let f = func!(|x: f64| x);
+        integrate_stratified(&f, 0.0, 1.0, 0, 10, 1);
+    }
+
+#[test]
+#[should_panic]
+    fn t_integrate_stratified_panic_2() {
+        let f = func!(|x: f64| x);
+        integrate_stratified(&f, 0.0, 1.0, 10, 0, 1);
+    }
+
+#[test]
+    fn t_riemann() {
+        let f = func!(|x: f64| x);
+        assert_eq!(left_riemann(&f, 0.0, 0.0, 10), 0.0);
+        assert_eq!(right_riemann(&f, 0.0, 1.0, 0), 0.0);
+        assert_fp!(left_riemann(&f, 0.0, 1.0, 4), 0.375, 1e-12);
+        assert_fp!(right_riemann(&f, 0.0, 1.0, 4), 0.625, 1e-12);
+        assert_fp!(left_riemann(&f, 1.0, 0.0, 4), -0.625, 1e-12);
+
+        // increasing functions
+        let funcs: Vec<Function> = vec![func!(|x: f64| x * x * x),
+                                        func!(|x: f64| x.exp()),
+                                        func!(|x: f64| x.sqrt())];
+        for f in &funcs {
+            let exact = integrate_wp(f, 0.0, 2.0, 10_000);
+            let mut last_left = f64::NEG_INFINITY;
+            let mut last_right = f64::INFINITY;
+            for p in [1, 2, 10, 100, 1_000] {
+                let left = left_riemann(f, 0.0, 2.0, p);
+                let right = right_riemann(f, 0.0, 2.0, p);
+                assert!(left < exact && exact < right);
+                assert!(left > last_left && right < last_right);
+                last_left = left;
+                last_right = right;
+            }
+
+            assert_fp!(left_riemann(f, 0.0, 2.0, 100_000), exact, 1e-3);
+            assert_fp!(right_riemann(f, 0.0, 2.0, 100_000), exact, 1e-3);
+        }
+
+        // decreasing function
+        let f = func!(|x: f64| (-x).exp());
+        let exact = 1.0 - (-1.0f64).exp();
+        assert!(left_riemann(&f, 0.0, 1.0, 10) > exact);
+        assert!(right_riemann(&f, 0.0, 1.0, 10) < exact);
+    }
+
+#[test]
+    fn t_integrate_polynomial() {
+        assert_eq!(integrate_polynomial(&[], 0.0, 1.0), 0.0);
+        assert_eq!(integrate_polynomial(&[1.0, 2.0], 1.0, 1.0), 0.0);
+        assert_eq!(integrate_polynomial(&[4.0, 1.0], 0.0, 1.0), 4.5);
+        assert_eq!(integrate_polynomial(&[0.0, 0.0, 1.0], 0.0, 3.0), 9.0);
+        assert_eq!(integrate_polynomial(&[0.0, 0.0, 1.0], 3.0, 0.0), -9.0);
+        assert_eq!(integrate_polynomial(&[0.0, 0.0, 0.0, 0.0, 1.0], -2.0, 2.0),
+                   64.0 / 5.0);
+
+        // Simpson's rule is exact for cubics, so the results should agree
+        let cubics: Vec<Vec<f64>> = vec![vec![1.0, 2.0, 3.0, 4.0],
+                                         vec![-5.0, 0.0, 0.5, -1.0],
+                                         vec![0.0, 7.0, 0.0, 0.0]];
+        for coeffs in cubics {
+            let c = coeffs.clone();
+            let f = func!(move |x: f64| c[0] + c[1] * x + c[2] * x * x + 
+                                        c[3] * x * x * x);
+            for &(a, b) in &[(0.0, 1.0), (-3.0, 2.0), (5.0, -1.5)] {
+                assert_fp!(integrate_polynomial(&coeffs, a, b), 
+                           integrate(&f, a, b), 1e-9);
+            }
+        }
+
+        // for higher degrees Simpson's rule only approximates the integral
+        let coeffs = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        let f = func!(|x: f64| 1.0 + x.powi(6));
+        let exact = integrate_polynomial(&coeffs, 0.0, 2.0);
+        assert_fp!(exact, 2.0 + 128.0 / 7.0, 1e-12);
+        assert!((integrate(&f, 0.0, 2.0) - exact).abs() > 1e-3);
+        assert_fp!(integrate_wp(&f, 0.0, 2.0, 1_000), exact, 1e-9);
+    }
+
 #[test]
 #[should_panic]
     fn t_integrate_panic() {
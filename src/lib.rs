@@ -34,20 +34,38 @@
 //! * `aliquot` -- Functions for calcuating aliquot sums, divisor sums,
 //!                and testing for perfect numbers and similar concepts.
 //!
+//! * `collatz` -- Iterate the Collatz function and count the steps
+//!                taken to reach one.
+//!
+//! * `combinatorics` -- Compute factorials, binomial coefficients,
+//!                      and Bernoulli numbers.
+//!
 //! * `continued_fraction` -- Generate and expand continued fractions.
 //!
 //! * `derivative` -- Estimate derivatives of functions, along with slope
 //!                   and concavity.
 //!
+//! * `digits` -- Convert integers between bases, and compute digit
+//!               sums, digital roots, palindromes, and repunits.
+//!
 //! * `factor` -- Compute the GCD, LCM, and prime factorization of numbers.
 //!
 //! * `figurate` -- Compute the value of various kinds of figurate numbers.
 //!
 //! * `func` -- Utility type alias and macro, used heavily in certain
-//!             other modules.
+//!             other modules, and approximate float comparison.
 //!
 //! * `integral` -- Estimate integrals of functions using numeric integration.
 //!
+//! * `lucky` -- Generate the lucky numbers with a sieve.
+//!
+//! * `modular` -- Modular exponentiation and inverses, the Chinese
+//!                Remainder Theorem, Legendre and Jacobi symbols, and
+//!                modular square roots.
+//!
+//! * `optimize` -- Minimize functions of several variables with
+//!                 gradient descent.
+//!
 //! * `partition` -- Compute the value of the number theory partition
 //!                  function.
 //!
@@ -56,8 +74,21 @@
 //!
 //! * `prime_count` -- Compute the value of the prime-counting function.
 //!
+//! * `rational` -- A rational number type for exact arithmetic.
+//!
 //! * `totient` -- Compute Euler's Totient Function.
 //!
+//! # Features
+//!
+//! A list of the optional features of this crate, none of which
+//! are enabled by default.
+//!
+//! * `num-bigint` -- Exact, arbitrary precision versions of some
+//!                   functions, such as `partition::part_via_bigint()`.
+//!
+//! * `serde` -- `Serialize` and `Deserialize` implementations for the
+//!              public structs and enums of this crate.
+//!
 //! # Examples
 //!
 //! ## Compute the number of primes under one million
@@ -147,17 +178,28 @@
 //! By changing the max value, it can be observed that the bias
 //! shrinks as the max grows.
 
+#[cfg(feature = "num-bigint")] extern crate num_bigint;
+#[cfg(feature = "serde")] extern crate serde;
+#[cfg(all(test, feature = "serde"))] extern crate serde_test;
+
 #[macro_use] mod macros;
 
 #[macro_use] pub mod func;
              pub mod aliquot;
+             pub mod collatz;
+             pub mod combinatorics;
              pub mod continued_fraction;
              pub mod derivative;
+             pub mod digits;
              pub mod factor;
              pub mod figurate;
              pub mod integral;
+             pub mod lucky;
+             pub mod modular;
+             pub mod optimize;
              pub mod partition;
 #[macro_use] pub mod prime;
              pub mod prime_count;
+             pub mod rational;
              pub mod totient;
 
@@ -0,0 +1,73 @@
+//! Module for working with lucky numbers.
+//!
+//! This module has a sieve for generating the lucky numbers,
+//! which are produced by a sieve similar to the Sieve of
+//! Eratosthenes, and share many properties with the primes.
+
+/// Return a `Vec<u64>` of the lucky numbers in [1, `max`].
+///
+/// The lucky numbers are generated by a sieve that removes values
+/// by their position in the list, rather than by their value. Starting
+/// with the odd numbers, the second remaining number, `3`, is taken,
+/// and every third remaining number is removed. Then the next remaining
+/// number, `7`, is taken, and every seventh remaining number is removed,
+/// and so on, until the number taken is larger than the list.
+///
+/// Every odd number in [1, `max`] is stored while sieving, so the
+/// memory used is linear in `max`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::lucky::lucky_numbers;
+/// assert_eq!(lucky_numbers(33), vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33]);
+/// ```
+pub fn lucky_numbers(max: u64) -> Vec<u64> {
+    let mut lucky: Vec<u64> = (1..=max).step_by(2).collect();
+
+    let mut i = 1;
+    while i < lucky.len() && lucky[i] as usize <= lucky.len() {
+        let step = lucky[i] as usize;
+
+        let mut pos = 0;
+        lucky.retain(|_| {
+            pos += 1;
+            pos % step != 0
+        });
+
+        i += 1;
+    }
+
+    lucky
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_lucky_numbers() {
+        assert_eq!(lucky_numbers(0), vec![]);
+        assert_eq!(lucky_numbers(1), vec![1]);
+        assert_eq!(lucky_numbers(2), vec![1]);
+        assert_eq!(lucky_numbers(3), vec![1, 3]);
+        assert_eq!(lucky_numbers(6), vec![1, 3]);
+        assert_eq!(lucky_numbers(7), vec![1, 3, 7]);
+        assert_eq!(lucky_numbers(33), vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33]);
+        assert_eq!(lucky_numbers(100),
+                   vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33, 37, 43, 49, 51,
+                        63, 67, 69, 73, 75, 79, 87, 93, 99]);
+
+        let lucky = lucky_numbers(100_000);
+        assert_eq!(lucky.len(), 8_772);
+        assert_eq!(lucky_numbers(1_000).len(), 153);
+        assert!(lucky.windows(2).all(|w| w[0] < w[1]));
+
+        // the lucky numbers up to any bound are a prefix of the rest
+        for max in [10, 100, 1_000, 10_000].iter().cloned() {
+            let prefix = lucky_numbers(max);
+            assert_eq!(prefix[..], lucky[..prefix.len()]);
+            assert!(lucky[prefix.len()] > max);
+        }
+    }
+}
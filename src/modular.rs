@@ -0,0 +1,520 @@
+//! Module for working with modular arithmetic.
+//!
+//! This module has functions for modular exponentiation and inverses,
+//! solving systems of congruences, computing the Legendre and Jacobi
+//! symbols, and finding modular square roots.
+
+use std::mem;
+use super::factor::{gcd, gcd_ext, isqrt};
+
+/// Return `base^exp mod modulus`.
+///
+/// This function uses exponentiation by squaring, with `u128`
+/// intermediate values so that no overflow can occur.
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::pow_mod;
+/// assert_eq!(pow_mod(4, 13, 497), 445);
+/// assert_eq!(pow_mod(2, 10, 1), 0);
+/// ```
+pub fn pow_mod(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must be positive!");
+
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    while exp != 0 {
+        if exp & 0x01 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+
+    result
+}
+
+// helper function to compute a * b mod m without overflow
+pub(crate) fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// Return the multiplicative inverse of `a` modulo `m`, that is, an
+/// `x` in `[0, m)` such that
+///
+/// ```text
+/// a * x = 1 (mod m)
+/// ```
+///
+/// or `None` if `a` and `m` are not coprime, in which case no inverse
+/// exists.
+///
+/// The inverse is found with `factor::gcd_ext()`.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::mod_inverse;
+/// assert_eq!(mod_inverse(3, 11), Some(4));
+/// assert_eq!(mod_inverse(6, 9), None);
+/// ```
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    assert!(m != 0, "modulus must be positive!");
+
+    let (g, x, _) = gcd_ext(a % m, m);
+    if g != 1 && m != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+/// Solve the system of congruences
+///
+/// ```text
+/// x = r_1 (mod m_1)
+/// x = r_2 (mod m_2)
+/// ...
+/// ```
+///
+/// where `residues` is a slice of `(r_i, m_i)` pairs, using the
+/// Chinese Remainder Theorem.
+///
+/// The solution is returned as a pair `(r, m)`, meaning every
+/// solution is of the form `x = r (mod m)`, where `m` is the LCM
+/// of the moduli and `r` is in `[0, m)`. The moduli need not be
+/// coprime, if the system is inconsistent `None` is returned.
+///
+/// An empty system is solved by every integer, so `(0, 1)`
+/// is returned.
+///
+/// # Panics
+///
+/// Panics if any of the moduli are zero, or if the LCM of the moduli
+/// is larger than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt(residues: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut r: u128 = 0;
+    let mut m: u128 = 1;
+
+    for &(ri, mi) in residues {
+        assert!(mi != 0, "modulus must be positive!");
+
+        let g = gcd(m as u64, mi) as u128;
+        let mi = mi as u128;
+        let diff = (ri as u128 % mi + mi - r % mi) % mi;
+        if diff % g != 0 {
+            return None;
+        }
+
+        // solve m * k = diff (mod mi) for k
+        let mg = mi / g;
+        let inv = mod_inverse(((m / g) % mg) as u64, mg as u64).unwrap();
+        let k = (diff / g) % mg * inv as u128 % mg;
+
+        r += m * k;
+        m *= mg;
+        assert!(m <= u64::MAX as u128, "LCM of moduli is larger than u64::MAX!");
+    }
+
+    Some((r as u64, m as u64))
+}
+
+/// Return the Legendre symbol `(a / p)`, where `p` is an odd prime.
+///
+/// The result is `1` if `a` is a nonzero quadratic residue modulo `p`,
+/// `-1` if it is a quadratic non-residue, and `0` if `p` divides `a`.
+///
+/// For an odd prime `p`, the Legendre symbol is equal to the Jacobi
+/// symbol, so this function calls `jacobi_symbol()`. Note that the
+/// primality of `p` is not checked, see `jacobi_symbol()` for the
+/// meaning of the result for composite `p`.
+///
+/// # Panics
+///
+/// Panics if `p` is even.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::legendre_symbol;
+/// assert_eq!(legendre_symbol(2, 7), 1);
+/// assert_eq!(legendre_symbol(3, 7), -1);
+/// assert_eq!(legendre_symbol(14, 7), 0);
+/// ```
+pub fn legendre_symbol(a: i64, p: u64) -> i8 {
+    jacobi_symbol(a, p)
+}
+
+/// Return the Jacobi symbol `(a / n)`, where `n` is a positive odd
+/// integer.
+///
+/// The Jacobi symbol is the product of the Legendre symbols `(a / p)`
+/// for every prime factor `p` of `n`. Unlike the Legendre symbol, a
+/// result of `1` does not mean `a` is a quadratic residue modulo `n`,
+/// but a result of `-1` does mean that `a` is a non-residue.
+///
+/// This function computes the symbol without factoring `n` by
+/// repeatedly applying the law of quadratic reciprocity, along with
+/// the supplementary law for `(2 / n)`.
+///
+/// # Panics
+///
+/// Panics if `n` is even.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::jacobi_symbol;
+/// assert_eq!(jacobi_symbol(1001, 9907), -1);
+/// assert_eq!(jacobi_symbol(2, 15), 1);
+/// assert_eq!(jacobi_symbol(-1, 15), -1);
+/// ```
+pub fn jacobi_symbol(a: i64, n: u64) -> i8 {
+    assert!(n & 0x01 == 1, "Jacobi symbol is undefined for even n = {}!", n);
+
+    let a = if a < 0 {
+        (n - a.unsigned_abs() % n) % n
+    } else {
+        a as u64 % n
+    };
+
+    jacobi(a, n)
+}
+
+// helper function to compute the Jacobi symbol of a non-negative `a`
+fn jacobi(mut a: u64, mut n: u64) -> i8 {
+    a %= n;
+
+    let mut t = 1;
+    while a != 0 {
+        while a & 0x01 == 0 {
+            a >>= 1;
+            if n & 0x07 == 3 || n & 0x07 == 5 {
+                t = -t;
+            }
+        }
+
+        mem::swap(&mut a, &mut n);
+        if a & 0x03 == 3 && n & 0x03 == 3 {
+            t = -t;
+        }
+        a %= n;
+    }
+
+    if n == 1 { t } else { 0 }
+}
+
+/// Return a square root of `n` modulo the prime `p`, that is,
+/// an `x` such that
+///
+/// ```text
+/// x^2 = n (mod p)
+/// ```
+///
+/// or `None` if `n` is a quadratic non-residue modulo `p`.
+///
+/// This function first checks that `n` is a quadratic residue using
+/// the Legendre symbol, then finds a root using the Tonelli-Shanks
+/// algorithm.
+///
+/// If `x` is a root, then so is `p - x`, the smaller of the two roots
+/// is returned.
+///
+/// Note that the primality of `p` is not checked. If `p` is a perfect
+/// square, `None` is returned immediately, as it has no non-residues
+/// to search for. Otherwise, the search for a non-residue is cut off
+/// after `2 * ln(p)^2` steps, which is always enough for a prime `p`
+/// under the generalized Riemann hypothesis, and the search for each
+/// `t^(2^i) = 1` is cut off after `s` steps, where `p - 1 = q * 2^s`.
+/// The root found is checked, so either a correct root or `None` is
+/// returned, but `None` may be returned even if a root exists.
+///
+/// # Panics
+///
+/// Panics if `p` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::modular::mod_sqrt;
+/// assert_eq!(mod_sqrt(10, 13), Some(6));
+/// assert_eq!(mod_sqrt(5, 7), None);
+/// ```
+pub fn mod_sqrt(n: u64, p: u64) -> Option<u64> {
+    assert!(p != 0, "modulus must be positive!");
+
+    let n = n % p;
+    if n == 0 || p == 2 {
+        return Some(n);
+    }
+
+    if jacobi(n, p) != 1 {
+        return None;
+    }
+
+    // write p - 1 as q * 2^s with q odd
+    let mut q = p - 1;
+    let mut s = 0;
+    while q & 0x01 == 0 {
+        q >>= 1;
+        s += 1;
+    }
+
+    // a perfect square has no quadratic non-residues
+    let root = isqrt(p);
+    if root * root == p {
+        return None;
+    }
+
+    // find a quadratic non-residue, which only fails for composite p
+    let ln_p = (p as f64).ln();
+    let limit = (2.0 * ln_p * ln_p) as u64 + 1;
+    let mut z = 2;
+    while z < p && z <= limit && jacobi(z, p) != -1 {
+        z += 1;
+    }
+    if z == p || z > limit {
+        return None;
+    }
+
+    let mut m = s;
+    let mut c = pow_mod(z, q, p);
+    let mut t = pow_mod(n, q, p);
+    let mut r = pow_mod(n, q / 2 + 1, p);
+
+    while t != 1 {
+        // find the least i such that t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 && i < m {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        if i == m {
+            return None;
+        }
+
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+
+    if mul_mod(r, r, p) != n {
+        return None;
+    }
+
+    Some(if r > p - r { p - r } else { r })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::prime::prime_sieve;
+
+    // reference implementation using Euler's criterion
+    fn euler_criterion(a: i64, p: u64) -> i8 {
+        let a = ((a % p as i64 + p as i64) % p as i64) as u64;
+        if a == 0 {
+            return 0;
+        }
+
+        let mut result = 1;
+        for _ in 0..((p - 1) / 2) {
+            result = result * a % p;
+        }
+
+        if result == 1 { 1 } else { -1 }
+    }
+
+#[test]
+    fn t_pow_mod() {
+        assert_eq!(pow_mod(0, 0, 7), 1);
+        assert_eq!(pow_mod(0, 5, 7), 0);
+        assert_eq!(pow_mod(3, 0, 7), 1);
+        assert_eq!(pow_mod(3, 4, 7), 4);
+        assert_eq!(pow_mod(4, 13, 497), 445);
+        assert_eq!(pow_mod(5, 100, 1), 0);
+        assert_eq!(pow_mod(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(pow_mod(2, 64, u64::MAX), 1);
+
+        for b in 0..20 {
+            for e in 0..10 {
+                assert_eq!(pow_mod(b, e, 1_000_007), 
+                           b.pow(e as u32) % 1_000_007);
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_pow_mod_panic() {
+        pow_mod(2, 2, 0);
+    }
+
+#[test]
+    fn t_mod_inverse() {
+        assert_eq!(mod_inverse(0, 1), Some(0));
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(0, 7), None);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(14, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(u64::MAX - 1, u64::MAX), Some(u64::MAX - 1));
+
+        for m in 2..200 {
+            for a in 0..m {
+                match mod_inverse(a, m) {
+                    Some(x) => assert_eq!(a * x % m, 1),
+                    None    => assert!(gcd(a, m) != 1),
+                }
+            }
+        }
+    }
+
+#[test]
+    fn t_crt() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(5, 7)]), Some((5, 7)));
+        assert_eq!(crt(&[(12, 7)]), Some((5, 7)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(3, 6), (3, 6), (0, 3)]), Some((3, 6)));
+        assert_eq!(crt(&[(0, 1 << 32), (1, (1 << 32) - 1)]),
+                   Some((1 << 32, u64::MAX - (1 << 32) + 1)));
+
+        for a in 0..12 {
+            for b in 0..18 {
+                let x = crt(&[(a, 12), (b, 18)]);
+                let brute = (0..36).find(|x| x % 12 == a && x % 18 == b);
+                assert_eq!(x, brute.map(|r| (r, 36)));
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_crt_panic() {
+        crt(&[(1, 3), (2, 0)]);
+    }
+
+#[test]
+#[should_panic]
+    fn t_crt_panic_2() {
+        crt(&[(1, 1 << 40), (2, (1 << 40) - 1)]);
+    }
+
+#[test]
+    fn t_legendre_symbol() {
+        assert_eq!(legendre_symbol(0, 3), 0);
+        assert_eq!(legendre_symbol(1, 3), 1);
+        assert_eq!(legendre_symbol(2, 3), -1);
+        assert_eq!(legendre_symbol(2, 7), 1);
+        assert_eq!(legendre_symbol(3, 7), -1);
+        assert_eq!(legendre_symbol(-3, 7), 1);
+        assert_eq!(legendre_symbol(21, 7), 0);
+
+        for p in prime_sieve(200).into_iter().skip(1) {
+            for a in -300..300 {
+                assert_eq!(legendre_symbol(a, p), euler_criterion(a, p));
+            }
+        }
+    }
+
+#[test]
+    fn t_jacobi_symbol() {
+        assert_eq!(jacobi_symbol(0, 1), 1);
+        assert_eq!(jacobi_symbol(5, 1), 1);
+        assert_eq!(jacobi_symbol(1001, 9907), -1);
+        assert_eq!(jacobi_symbol(19, 45), 1);
+        assert_eq!(jacobi_symbol(8, 21), -1);
+        assert_eq!(jacobi_symbol(5, 21), 1);
+        assert_eq!(jacobi_symbol(6, 21), 0);
+        assert_eq!(jacobi_symbol(i64::MIN, 3), 1);
+        assert_eq!(jacobi_symbol(i64::MAX, u64::MAX), -1);
+
+        // the Jacobi symbol is multiplicative in n
+        for a in -50..50 {
+            for m in (3..40).step_by(2) {
+                for n in (3..40).step_by(2) {
+                    assert_eq!(jacobi_symbol(a, m * n),
+                               jacobi_symbol(a, m) * jacobi_symbol(a, n));
+                }
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_jacobi_symbol_panic() {
+        jacobi_symbol(3, 10);
+    }
+
+#[test]
+    fn t_mod_sqrt() {
+        assert_eq!(mod_sqrt(0, 13), Some(0));
+        assert_eq!(mod_sqrt(26, 13), Some(0));
+        assert_eq!(mod_sqrt(1, 2), Some(1));
+        assert_eq!(mod_sqrt(10, 13), Some(6));
+        assert_eq!(mod_sqrt(5, 7), None);
+        assert_eq!(mod_sqrt(2, 7), Some(3));
+
+        // 2^64 - 59 is the largest prime below 2^64
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(mod_sqrt(5, p), None);
+        let r = mod_sqrt(6, p).unwrap();
+        assert_eq!(mul_mod(r, r, p), 6);
+
+        for p in prime_sieve(500) {
+            for n in 0..p {
+                match mod_sqrt(n, p) {
+                    Some(r) => {
+                        assert_eq!(r * r % p, n);
+                        assert!(r <= p - r);
+                    }
+                    None    => assert_eq!(legendre_symbol(n as i64, p), -1),
+                }
+            }
+        }
+
+        // 2^16 + 1 = 65537 exercises the Tonelli-Shanks loop
+        for n in 1..2_000 {
+            if let Some(r) = mod_sqrt(n, 65_537) {
+                assert_eq!(r * r % 65_537, n);
+            }
+        }
+
+        // composite moduli either give a correct root or None
+        mod_sqrt(4, 9);
+        mod_sqrt(16, 1 << 20);
+        mod_sqrt(u64::MAX - 1, u64::MAX);
+        assert_eq!(mod_sqrt(2, 1_000_003 * 1_000_003), None);
+        assert_eq!(mod_sqrt(4, 4_294_967_291 * 4_294_967_291), None);
+        for p in 3..300 {
+            for n in 0..p {
+                if let Some(r) = mod_sqrt(n, p) {
+                    assert_eq!(r * r % p, n);
+                }
+            }
+        }
+    }
+}
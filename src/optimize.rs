@@ -0,0 +1,78 @@
+//! Module for numeric optimization.
+//!
+//! This module has functions for minimizing functions of
+//! several variables using the numeric derivatives of the
+//! `derivative` module.
+
+pub use super::func::*;
+
+use super::derivative::gradient_n;
+
+/// Return the point found by minimizing `f` with gradient descent,
+/// starting at `start`.
+///
+/// Each iteration estimates the gradient of `f` with `gradient_n()`,
+/// and takes a step of `lr` times the gradient in the opposite
+/// direction. The point is returned after `iters` iterations, so
+/// the result is only an estimate of a local minimum, which may not
+/// have been reached if `lr` is too small or `iters` is too low.
+/// If `lr` is too large, the steps can overshoot the minimum and
+/// diverge.
+///
+/// If `iters` is zero, a copy of `start` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::optimize::*;
+///
+/// let f: FunctionN = Rc::new(|x: &[f64]| (x[0] - 3.0).powi(2) + x[1] * x[1]);
+/// let min = minimize(&f, &[0.0, 1.0], 0.1, 100);
+/// assert!((min[0] - 3.0).abs() < 1.0e-6);
+/// assert!(min[1].abs() < 1.0e-6);
+/// ```
+pub fn minimize(f: &FunctionN, start: &[f64], lr: f64, iters: u32) -> Vec<f64> {
+    let mut point = start.to_vec();
+    for _ in 0..iters {
+        let gradient = gradient_n(f, &point);
+        for (x, slope) in point.iter_mut().zip(gradient) {
+            *x -= lr * slope;
+        }
+    }
+
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_minimize() {
+        let f: FunctionN = Rc::new(|x: &[f64]| {
+            (x[0] - 3.0).powi(2) + (x[1] + 1.0).powi(2)
+        });
+
+        let min = minimize(&f, &[0.0, 0.0], 0.1, 200);
+        assert_fp!(min[0], 3.0, 1.0e-6);
+        assert_fp!(min[1], -1.0, 1.0e-6);
+
+        let min = minimize(&f, &[-50.0, 20.0], 0.25, 200);
+        assert_fp!(min[0], 3.0, 1.0e-6);
+        assert_fp!(min[1], -1.0, 1.0e-6);
+
+        assert_eq!(minimize(&f, &[0.5, 0.5], 0.1, 0), vec![0.5, 0.5]);
+
+        // a learning rate that is too large diverges
+        let min = minimize(&f, &[0.0, 0.0], 1.5, 50);
+        assert!((min[0] - 3.0).abs() > 1.0);
+
+        // Rosenbrock function, minimum at (1, 1)
+        let f: FunctionN = Rc::new(|x: &[f64]| {
+            (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+        });
+        let min = minimize(&f, &[0.0, 0.0], 0.001, 50_000);
+        assert_fp!(min[0], 1.0, 1.0e-3);
+        assert_fp!(min[1], 1.0, 1.0e-3);
+    }
+}
@@ -5,6 +5,9 @@
 
 use super::figurate::general_pentagonal_number as gpn;
 
+#[cfg(feature = "num-bigint")]
+use num_bigint::BigUint;
+
 /// Max partition size the functions will calculate
 ///
 /// This limit is put in place to prevent stack and
@@ -104,6 +107,153 @@ pub fn part(n: i64) -> u64 {
     part_wc(n, &mut cache)
 }
 
+/// Return a `Vec<u64>` of the partition function of every value
+/// in [0, max], that is, `p(0), p(1), ..., p(max)`.
+///
+/// This function uses the same recurrence as `part_wc()`, but builds
+/// the table iteratively from the bottom up in a single pass, which
+/// is more efficient than calling `part()` for each value.
+///
+/// If `max` is negative, an empty `Vec` is returned.
+///
+/// # Panics
+/// 
+/// Panics if `max` is greater than `MAX_PART`.
+/// This limit is put in place to prevent integer overflows.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::partition::partition_table;
+/// assert_eq!(partition_table(5), vec![1, 1, 2, 3, 5, 7]);
+/// ```
+pub fn partition_table(max: i64) -> Vec<u64> {
+    assert!(max <= MAX_PART, "max value of {} is larger than MAX_PART!", max);
+    if max < 0 {
+        return Vec::new();
+    }
+
+    let mut table: Vec<u64> = Vec::with_capacity(max as usize + 1);
+    table.push(1);
+    for n in 1..(max + 1) {
+        let mut part: u64 = 0;
+        let mut pent_n: i64 = 1;
+        loop {
+            let pent = gpn(pent_n);
+            if pent > n {
+                break;
+            }
+
+            if (pent_n - 1) & 0x03 < 2 {
+                part += table[(n - pent) as usize];
+            } else {
+                part -= table[(n - pent) as usize];
+            }
+
+            pent_n += 1;
+        }
+
+        table.push(part);
+    }
+
+    table
+}
+
+/// Return the number of partitions of `n` in which every part is
+/// at most `max_part`.
+///
+/// The count is built up with a table of the partitions of each
+/// value in [0, n], adding the allowed parts one at a time, which
+/// takes `O(n * max_part)` time. If `max_part` is at least `n`, this
+/// is the same as `part(n)`.
+///
+/// If `n` is negative, zero is returned. The empty partition of zero
+/// is counted for any `max_part`.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than `MAX_PART`.
+/// This limit is put in place to prevent integer overflows.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::partition::part_max_part;
+/// assert_eq!(part_max_part(5, 3), 5);
+/// assert_eq!(part_max_part(5, 1), 1);
+/// assert_eq!(part_max_part(5, 5), 7);
+/// ```
+pub fn part_max_part(n: i64, max_part: i64) -> u64 {
+    assert!(n <= MAX_PART, "n value of {} is larger than MAX_PART!", n);
+    if n < 0 {
+        return 0;
+    }
+
+    let n = n as usize;
+    let mut table: Vec<u64> = vec![0; n + 1];
+    table[0] = 1;
+    for k in 1..(max_part.min(n as i64).max(0) as usize + 1) {
+        for m in k..(n + 1) {
+            table[m] += table[m - k];
+        }
+    }
+
+    table[n]
+}
+
+/// Calculate the partition function of `n` exactly, returning the
+/// result as a decimal string.
+///
+/// Unlike `part()`, this function is not limited by `MAX_PART`, as 
+/// it uses arbitrary precision integers from `num-bigint`, and computes
+/// the values from the bottom up, rather than recursively. It is much
+/// slower than `part()`, but is useful for checking its results and for
+/// computing very large partition values.
+///
+/// If `n` is negative, `"0"` is returned.
+///
+/// This function is only available with the `num-bigint` feature.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::partition::part_via_bigint;
+/// assert_eq!(part_via_bigint(100), "190569292");
+/// assert_eq!(part_via_bigint(1_000), "24061467864032622473692149727991");
+/// ```
+#[cfg(feature = "num-bigint")]
+pub fn part_via_bigint(n: i64) -> String {
+    if n < 0 {
+        return String::from("0");
+    }
+
+    let mut values: Vec<BigUint> = vec![BigUint::from(1u32)];
+    for m in 1..(n + 1) {
+        let mut add = BigUint::from(0u32);
+        let mut sub = BigUint::from(0u32);
+
+        let mut pent_n: i64 = 1;
+        loop {
+            let pent = gpn(pent_n);
+            if pent > m {
+                break;
+            }
+
+            if (pent_n - 1) & 0x03 < 2 {
+                add += &values[(m - pent) as usize];
+            } else {
+                sub += &values[(m - pent) as usize];
+            }
+
+            pent_n += 1;
+        }
+
+        values.push(add - sub);
+    }
+
+    values[n as usize].to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,6 +280,75 @@ mod tests {
         part(MAX_PART);
     }
 
+#[test]
+    fn t_partition_table() {
+        assert_eq!(partition_table(-1), vec![]);
+        assert_eq!(partition_table(0), vec![1]);
+        assert_eq!(partition_table(5), vec![1, 1, 2, 3, 5, 7]);
+
+        let table = partition_table(100);
+        assert_eq!(table.len(), 101);
+        assert_eq!(table[100], 190569292);
+
+        let table = partition_table(MAX_PART);
+        assert_eq!(table.len(), MAX_PART as usize + 1);
+        for n in 0..(MAX_PART + 1) {
+            assert_eq!(table[n as usize], part(n));
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_partition_table_panic() {
+        partition_table(MAX_PART + 1);
+    }
+
+#[test]
+    fn t_part_max_part() {
+        assert_eq!(part_max_part(-1, 3), 0);
+        assert_eq!(part_max_part(0, 0), 1);
+        assert_eq!(part_max_part(0, 3), 1);
+        assert_eq!(part_max_part(5, -1), 0);
+        assert_eq!(part_max_part(5, 0), 0);
+        assert_eq!(part_max_part(5, 1), 1);
+        assert_eq!(part_max_part(5, 2), 3);
+        assert_eq!(part_max_part(5, 3), 5);
+        assert_eq!(part_max_part(5, 4), 6);
+        assert_eq!(part_max_part(5, 5), 7);
+        assert_eq!(part_max_part(5, 100), 7);
+        assert_eq!(part_max_part(100, 2), 51);
+
+        let table = partition_table(MAX_PART);
+        for n in [0, 1, 10, 100, MAX_PART] {
+            assert_eq!(part_max_part(n, n), table[n as usize]);
+            assert_eq!(part_max_part(n, i64::MAX), table[n as usize]);
+        }
+
+        // parts of at most two are some number of twos, filled with ones
+        for n in 0..(MAX_PART + 1) {
+            assert_eq!(part_max_part(n, 2), n as u64 / 2 + 1);
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_part_max_part_panic() {
+        part_max_part(MAX_PART + 1, 1);
+    }
+
+#[test]
+#[cfg(feature = "num-bigint")]
+    fn t_part_via_bigint() {
+        assert_eq!(part_via_bigint(-1), "0");
+        assert_eq!(part_via_bigint(0), "1");
+
+        for n in 0..(MAX_PART + 1) {
+            assert_eq!(part_via_bigint(n), part(n).to_string());
+        }
+
+        assert_eq!(part_via_bigint(1_000), "24061467864032622473692149727991");
+    }
+
 #[test]
 #[should_panic]
     fn t_part_panic() {
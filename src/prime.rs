@@ -4,6 +4,16 @@
 //! using a variety of different sieves, testing if numbers
 //! are prime or composite, and preforming simple factorizations.
 
+use super::factor;
+use super::modular;
+use super::modular::mul_mod;
+use super::prime_count;
+
+use std::ops::ControlFlow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Return a `Vec<u64>` of the primes in [1, `max_u64`] using the 
 /// Sieve of Atkin.
 ///
@@ -146,6 +156,63 @@ pub fn eratosthenes(max_u64: u64) -> Vec<u64> {
     primes
 }
 
+/// Return a `Vec<u64>` of the primes in [1, `max_u64`] using a Sieve
+/// of Eratosthenes over only the odd numbers.
+///
+/// Bit `i` of the sieve represents the odd value `2i + 1`, so the
+/// sieve uses half of the memory of `eratosthenes()`, and has better
+/// cache behavior as a result. The output is identical.
+///
+/// # Panics
+///
+/// Panics if `max_u64` cannot be cast into a `usize`.
+///
+/// Can panic if `max_u64` is so large that not enough
+/// memory can be allocated for the sieve.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::eratosthenes_odd;
+/// assert_eq!(eratosthenes_odd(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// ```
+pub fn eratosthenes_odd(max_u64: u64) -> Vec<u64> {
+    assert!(max_u64 < ::std::usize::MAX as u64, 
+            "sieve max {} is larger than machine word size!", max_u64);
+    let max = max_u64 as usize;
+
+    if max < 2 {
+        return Vec::new();
+    }
+
+    let size = (max - 1) / 2 + 1;
+    let mut sieve = Bitset::new(size);
+    sieve.one();
+    sieve.set(0, false);
+
+    let mut primes: Vec<u64> = vec![2];
+    for i in 1..size {
+        if !sieve.read(i) {
+            continue;
+        }
+
+        let pos = 2 * i + 1;
+        primes.push(pos as u64);
+
+        if pos > max / pos {
+            continue;
+        }
+
+        let mut not_prime = (pos * pos - 1) / 2;
+        while not_prime < size {
+            sieve.set(not_prime, false);
+            not_prime += pos;
+        }
+    }
+
+    primes
+}
+
 /// Size of the segmented sieve segments in `segmented_eratosthenes()`
 ///
 /// Also used to determine when `prime_sieve()` should
@@ -332,6 +399,33 @@ pub fn nth_prime(n: u64) -> u64 {
     panic!("Nth prime of N = {} is larger than u64::MAX!", n);
 }
 
+/// Return the index of `p` in the sequence of primes, starting with
+/// `P0 = 2`, or `None` if `p` is not prime.
+///
+/// This is the inverse of `nth_prime()`, and is computed as one less
+/// than the value of the prime-counting function at `p`.
+///
+/// # Panics
+///
+/// Panics if `prime_count::prime_count()` panics, see the documentation
+/// for this function for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::prime_index;
+/// assert_eq!(prime_index(7), Some(3));
+/// assert_eq!(prime_index(97), Some(24));
+/// assert_eq!(prime_index(100), None);
+/// ```
+pub fn prime_index(p: u64) -> Option<u64> {
+    if !is_prime(p) {
+        return None;
+    }
+
+    Some(prime_count::prime_count(p) - 1)
+}
+
 /// Idiomatic prime sieve, returns a `Vec<u64>` of primes in [1, max].
 ///
 /// If you want to generate primes, this is probably the function
@@ -342,7 +436,8 @@ pub fn nth_prime(n: u64) -> u64 {
 /// `segmented_eratosthenes()`.
 ///
 /// See `atkin()` and `segmented_eratosthenes()` for more
-/// information.
+/// information, and `prime_sieve_with_threshold()` to change
+/// where the switch between them happens.
 ///
 /// # Panics
 /// 
@@ -355,13 +450,197 @@ pub fn nth_prime(n: u64) -> u64 {
 /// assert_eq!(prime_sieve(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
 /// ```
 pub fn prime_sieve(max: u64) -> Vec<u64> { 
-    if max < S_SIEVE_SIZE { // 2^16
+    prime_sieve_with_threshold(max, S_SIEVE_SIZE)
+}
+
+/// Return the sum of every prime in [1, max].
+///
+/// The primes are generated with the `segmented_sieve!` macro, so 
+/// they are never stored, and the sum is kept in a `u128` so that
+/// it cannot overflow.
+///
+/// # Panics
+/// 
+/// Panics if `prime_sieve()` panics. See the documentation of 
+/// `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::prime_sum;
+/// assert_eq!(prime_sum(10), 17);
+/// assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+/// ```
+pub fn prime_sum(max: u64) -> u128 {
+    if max < 2 {
+        return 0;
+    }
+
+    let mut sum: u128 = 2;
+    segmented_sieve!(max, candidate, { sum += candidate as u128; });
+
+    sum
+}
+
+/// Return the number of primes in [1, max].
+///
+/// The primes are generated with the `segmented_sieve!` macro and
+/// only counted, so they are never stored. This is much slower than
+/// `prime_count::prime_count()`, but is useful for checking its
+/// results.
+///
+/// # Panics
+/// 
+/// Panics if `prime_sieve()` panics. See the documentation of 
+/// `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::count_primes;
+/// assert_eq!(count_primes(10), 4);
+/// assert_eq!(count_primes(1_000_000), 78_498);
+/// ```
+pub fn count_primes(max: u64) -> u64 {
+    if max < 2 {
+        return 0;
+    }
+
+    let mut count: u64 = 1;
+    segmented_sieve!(max, candidate, { count += 1; });
+
+    count
+}
+
+/// Call `f` with every prime in [1, max] in increasing order,
+/// stopping early if `f` returns `ControlFlow::Break`.
+///
+/// The primes are generated with the `segmented_sieve!` macro, so
+/// they are never stored. Unlike using the macro directly, the
+/// sieve can be stopped without returning from the calling function.
+///
+/// # Panics
+/// 
+/// Panics if `prime_sieve()` panics. See the documentation of 
+/// `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use reikna::prime::for_each_prime;
+///
+/// let mut primes: Vec<u64> = Vec::new();
+/// for_each_prime(1_000_000, |p| {
+///     if p > 20 {
+///         return ControlFlow::Break(());
+///     }
+///     primes.push(p);
+///     ControlFlow::Continue(())
+/// });
+/// assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// ```
+pub fn for_each_prime<F>(max: u64, mut f: F)
+    where F: FnMut(u64) -> ControlFlow<()> {
+    if max < 2 || f(2).is_break() {
+        return;
+    }
+
+    segmented_sieve!(max, candidate, {
+                                        if f(candidate).is_break() {
+                                            return;
+                                        }
+                                     });
+}
+
+/// Return a `Vec<u64>` of primes in [1, max], using `threshold` to
+/// choose the sieve.
+///
+/// This function works in the same way as `prime_sieve()`, but
+/// uses `atkin()` if `max` is less than `threshold` rather than
+/// `S_SIEVE_SIZE`, otherwise it will use `segmented_eratosthenes()`.
+/// This allows the switch between the two sieves to be tuned to
+/// the machine being used.
+///
+/// # Panics
+/// 
+/// Panics if `max` is too large to cast into a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::prime_sieve_with_threshold;
+/// assert_eq!(prime_sieve_with_threshold(20, 0), 
+///            vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// ```
+pub fn prime_sieve_with_threshold(max: u64, threshold: u64) -> Vec<u64> {
+    if max < threshold {
         return atkin(max);
     }
 
     segmented_eratosthenes(max)
 }
 
+/// Return a `Vec` of every prime constellation with the offsets in 
+/// `pattern` whose members are all in [1, max].
+///
+/// A prime constellation is a set of primes of the form
+///
+/// ```text
+/// { p + pattern[0], p + pattern[1], ..., p + pattern[k - 1] }
+/// ```
+///
+/// For example, the pattern `[0, 2]` gives the twin primes, and
+/// the pattern `[0, 2, 6, 8]` gives the prime quadruplets.
+///
+/// Each constellation is returned as a `Vec<u64>` with its members
+/// in the same order as `pattern`, and the constellations are sorted
+/// by `p`. All primality checks are done with a single call to 
+/// `prime_sieve(max)`.
+///
+/// # Panics
+/// 
+/// Panics if `pattern` is empty, or if `prime_sieve()` panics. See
+/// the documentation of `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::prime_constellations;
+/// assert_eq!(prime_constellations(20, &[0, 2]), 
+///            vec![vec![3, 5], vec![5, 7], vec![11, 13], vec![17, 19]]);
+/// assert_eq!(prime_constellations(20, &[0, 2, 6, 8]), 
+///            vec![vec![5, 7, 11, 13], vec![11, 13, 17, 19]]);
+/// ```
+pub fn prime_constellations(max: u64, pattern: &[u64]) -> Vec<Vec<u64>> {
+    assert!(!pattern.is_empty(), "pattern cannot be empty!");
+
+    let primes = prime_sieve(max);
+    let mut is_p = Bitset::new(max as usize + 1);
+    for p in &primes {
+        is_p.set(*p as usize, true);
+    }
+
+    let check = |val: Option<u64>| match val {
+        Some(v) if v <= max => is_p.read(v as usize),
+        _ => false,
+    };
+
+    let mut constellations: Vec<Vec<u64>> = Vec::new();
+    for q in &primes {
+        if *q < pattern[0] {
+            continue;
+        }
+
+        let p = *q - pattern[0];
+        if pattern.iter().all(|off| check(p.checked_add(*off))) {
+            constellations.push(pattern.iter().map(|off| p + off).collect());
+        }
+    }
+
+    constellations
+}
+
 /// Return `true` if `value` is prime, and false if it is composite.
 ///
 /// This function works by checking if `value` is a small prime,
@@ -405,6 +684,437 @@ pub fn is_prime(value: u64) -> bool {
     true
 }
 
+/// The reason a value is or is not prime, see `primality_reason()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Primality {
+    /// The value is prime.
+    Prime,
+    /// The value is zero or one, which are neither prime nor composite.
+    Unit,
+    /// The value is even, and larger than two.
+    Even,
+    /// The value is odd and composite, and this is its smallest factor.
+    DivisibleBy(u64),
+}
+
+/// Return the `Primality` of `value`, that is, whether it is prime,
+/// and if not, the reason why.
+///
+/// Zero and one are reported as `Primality::Unit`, even values larger
+/// than two as `Primality::Even`, and odd composite values with their
+/// smallest prime factor as `Primality::DivisibleBy`.
+///
+/// Like `is_prime()`, this function works by trial division, so it
+/// takes time proportional to the square root of `value` for primes.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::{primality_reason, Primality};
+/// assert_eq!(primality_reason(1), Primality::Unit);
+/// assert_eq!(primality_reason(4), Primality::Even);
+/// assert_eq!(primality_reason(9), Primality::DivisibleBy(3));
+/// assert_eq!(primality_reason(7), Primality::Prime);
+/// ```
+pub fn primality_reason(value: u64) -> Primality {
+    if value < 2 {
+        return Primality::Unit;
+    }
+
+    if value == 2 {
+        return Primality::Prime;
+    }
+
+    if value & 0x01 == 0 {
+        return Primality::Even;
+    }
+
+    let mut test_fac = 3;
+    while test_fac <= value / test_fac {
+        if value % test_fac == 0 {
+            return Primality::DivisibleBy(test_fac);
+        }
+        test_fac += 2;
+    }
+
+    Primality::Prime
+}
+
+/// Return `true` if `value` is prime, and false if it is composite,
+/// using the Baillie-PSW primality test.
+///
+/// After trial division by a few small primes, `value` is checked
+/// with a strong Fermat test to base two, followed by a strong Lucas
+/// test using Selfridge's choice of parameters, that is, the first
+/// `D` in `5, -7, 9, -11, ...` with Jacobi symbol `(D / value) = -1`,
+/// and
+///
+/// ```text
+/// P = 1, Q = (1 - D) / 4
+/// ```
+///
+/// No composite below `2^64` passes both tests, so unlike `is_prime()`
+/// the result is exact for every `u64` while taking time logarithmic
+/// in `value`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::is_prime_bpsw;
+/// assert_eq!(is_prime_bpsw(97), true);
+/// assert_eq!(is_prime_bpsw(2047), false);
+/// assert_eq!(is_prime_bpsw(18_446_744_073_709_551_557), true);
+/// ```
+pub fn is_prime_bpsw(value: u64) -> bool {
+    if value < 2 {
+        return false;
+    }
+
+    for p in &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47] {
+        if value % p == 0 {
+            return value == *p;
+        }
+    }
+
+    if value < 53 * 53 {
+        return true;
+    }
+
+    strong_fermat(value, 2) && strong_lucas(value)
+}
+
+/// Return `true` if `n` is a Carmichael number, that is, a composite
+/// number `n` with `a^(n - 1) = 1 mod n` for every `a` coprime to `n`.
+///
+/// This function uses Korselt's criterion, that `n` is a Carmichael
+/// number exactly when it is composite and squarefree, and `p - 1`
+/// divides `n - 1` for every prime `p` dividing `n`. As every
+/// Carmichael number is odd and is a Fermat pseudoprime to base two,
+/// values that fail either test are rejected before being factored.
+/// Primality is tested with `is_prime_bpsw()`, and the factors are
+/// found with `factor::factorize_exp()`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::is_carmichael;
+/// assert_eq!(is_carmichael(561), true); // 3 * 11 * 17
+/// assert_eq!(is_carmichael(560), false);
+/// assert_eq!(is_carmichael(563), false); // prime
+/// ```
+pub fn is_carmichael(n: u64) -> bool {
+    if n < 3 || n & 0x01 == 0 {
+        return false;
+    }
+
+    if modular::pow_mod(2, n - 1, n) != 1 || is_prime_bpsw(n) {
+        return false;
+    }
+
+    factor::factorize_exp(n).iter().all(|&(p, e)| {
+        e == 1 && (n - 1) % (p - 1) == 0
+    })
+}
+
+// bases for the Miller-Rabin test in PrimalityTester, testing with
+// every prime up to 37 makes the test exact for every u64
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31,
+                                       37];
+
+/// A primality tester that keeps a list of small primes between calls.
+///
+/// `is_prime()` tests every candidate factor up to the square root of
+/// its argument on each call. A `PrimalityTester` sieves the primes up
+/// to a bound once, when it is created, and tests values by trial
+/// division with these primes. Values that are not resolved by the
+/// primes, that is, values larger than the square of the bound that
+/// have no small factors, are tested with a Miller-Rabin test to each
+/// prime base up to `37`, which is exact for every `u64`.
+///
+/// This is well suited to testing many values of a similar size,
+/// as the bound can be chosen to suit the values.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::PrimalityTester;
+/// let tester = PrimalityTester::new(1_000);
+/// assert_eq!(tester.is_prime(97), true);
+/// assert_eq!(tester.is_prime(999_983), true);
+/// assert_eq!(tester.is_prime(1_000_000_007 * 3), false);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrimalityTester {
+    bound: u64,
+    primes: Vec<u64>,
+}
+
+impl PrimalityTester {
+    /// Return a new `PrimalityTester`, sieving the primes in [1, `bound`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prime_sieve()` panics, see the documentation for
+    /// this function for more information.
+    pub fn new(bound: u64) -> PrimalityTester {
+        PrimalityTester { bound, primes: prime_sieve(bound) }
+    }
+
+    /// Return the bound the primes of this tester were sieved to.
+    pub fn bound(&self) -> u64 {
+        self.bound
+    }
+
+    /// Return `true` if `value` is prime, and false if it is composite.
+    ///
+    /// The result is exact for every `u64`.
+    pub fn is_prime(&self, value: u64) -> bool {
+        if value < 2 {
+            return false;
+        }
+
+        for p in &self.primes {
+            if *p > value / *p {
+                return true;
+            }
+
+            if value % *p == 0 {
+                return value == *p;
+            }
+        }
+
+        if (self.bound as u128) * (self.bound as u128) >= value as u128 {
+            return true;
+        }
+
+        if value & 0x01 == 0 {
+            return value == 2;
+        }
+
+        MILLER_RABIN_BASES.iter().all(|base| {
+            *base % value == 0 || strong_fermat(value, *base)
+        })
+    }
+}
+
+impl Default for PrimalityTester {
+    /// Return a new `PrimalityTester` using `S_SIEVE_SIZE` as the bound.
+    fn default() -> PrimalityTester {
+        PrimalityTester::new(S_SIEVE_SIZE)
+    }
+}
+
+// helper function to compute a + b mod m without overflow
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+// helper function to compute x / 2 mod the odd modulus m
+fn half_mod(x: u64, m: u64) -> u64 {
+    if x & 0x01 == 0 {
+        x / 2
+    } else {
+        ((x as u128 + m as u128) / 2) as u64
+    }
+}
+
+// helper function to preform a strong Fermat test to base `base`
+// on the odd value n
+fn strong_fermat(n: u64, base: u64) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut x = modular::pow_mod(base, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+
+    false
+}
+
+// helper function to preform a strong Lucas test on the odd value n,
+// using Selfridge's parameters
+fn strong_lucas(n: u64) -> bool {
+    // perfect squares have no D with (D / n) = -1
+    let root = (n as f64).sqrt() as u64;
+    if (root.saturating_sub(1)..(root + 2)).any(|r| r as u128 * r as u128 == 
+                                                     n as u128) {
+        return false;
+    }
+
+    let mut d: i64 = 5;
+    loop {
+        match modular::jacobi_symbol(d, n) {
+            -1 => break,
+            0 if d.unsigned_abs() != n => return false,
+            _ => (),
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+
+    // reduce D and Q = (1 - D) / 4 modulo n
+    let to_mod = |x: i64| if x < 0 {
+        (n - x.unsigned_abs() % n) % n
+    } else {
+        x as u64 % n
+    };
+    let dm = to_mod(d);
+    let q = to_mod((1 - d) / 4);
+
+    let s = (n + 1).trailing_zeros();
+    let k = (n + 1) >> s;
+
+    // compute U_k, V_k, and Q^k with the binary method, P = 1
+    let mut u = 1;
+    let mut v = 1;
+    let mut qk = q;
+    for bit in (0..(63 - k.leading_zeros())).rev() {
+        u = mul_mod(u, v, n);
+        v = add_mod(mul_mod(v, v, n), mul_mod(2, n - qk, n), n);
+        qk = mul_mod(qk, qk, n);
+
+        if (k >> bit) & 0x01 == 1 {
+            let nu = half_mod(add_mod(u, v, n), n);
+            let nv = half_mod(add_mod(mul_mod(dm, u, n), v, n), n);
+            u = nu;
+            v = nv;
+            qk = mul_mod(qk, q, n);
+        }
+    }
+
+    if u == 0 || v == 0 {
+        return true;
+    }
+
+    for _ in 1..s {
+        v = add_mod(mul_mod(v, v, n), mul_mod(2, n - qk, n), n);
+        if v == 0 {
+            return true;
+        }
+        qk = mul_mod(qk, qk, n);
+    }
+
+    false
+}
+
+/// Return the norm of the Gaussian integer `a + bi`, that is,
+/// `a^2 + b^2`.
+///
+/// The norm is returned as a `u128`, as it can be larger than
+/// `u64::MAX` for large `a` and `b`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::gaussian_norm;
+/// assert_eq!(gaussian_norm(3, -4), 25);
+/// ```
+pub fn gaussian_norm(a: i64, b: i64) -> u128 {
+    let a = a.unsigned_abs() as u128;
+    let b = b.unsigned_abs() as u128;
+    a * a + b * b
+}
+
+/// Return `true` if the Gaussian integer `a + bi` is a Gaussian prime.
+///
+/// A Gaussian integer with both parts nonzero is prime if and only if
+/// its norm, `a^2 + b^2`, is a prime. A Gaussian integer with one part
+/// equal to zero is prime if and only if the absolute value of the other
+/// part is a prime congruent to `3 mod 4`.
+///
+/// Primality is tested with `is_prime()`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` are both nonzero and the norm of `a + bi`
+/// is larger than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::is_gaussian_prime;
+/// assert_eq!(is_gaussian_prime(1, 1), true);
+/// assert_eq!(is_gaussian_prime(3, 0), true);
+/// assert_eq!(is_gaussian_prime(5, 0), false);
+/// ```
+pub fn is_gaussian_prime(a: i64, b: i64) -> bool {
+    if a == 0 || b == 0 {
+        let p = (a | b).unsigned_abs();
+        return p & 0x03 == 3 && is_prime(p);
+    }
+
+    let norm = gaussian_norm(a, b);
+    assert!(norm <= u64::MAX as u128,
+            "norm of {} + {}i is larger than u64::MAX!", a, b);
+
+    is_prime(norm as u64)
+}
+
+/// Return `Ok(())` if `value` is prime, or `Err(d)` where `d` is
+/// a nontrivial divisor of `value` if it is composite.
+///
+/// This is a more informative version of `is_prime()`, useful
+/// for explaining why a number is composite.
+///
+/// The divisor is found by trial division up to `factor::MAX_SMALL_NUM`,
+/// meaning the smallest factor is returned for most values. If no
+/// small factor is found and `value` is composite, the divisor is
+/// found with Pollard's Rho Algorithm (see `factor::rho()`), and
+/// may not be the smallest factor or even prime.
+///
+/// `0` and `1` are not prime but have no nontrivial divisors,
+/// so `Err(value)` is returned for them.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::primality_witness;
+/// assert_eq!(primality_witness(97), Ok(()));
+/// assert_eq!(primality_witness(91), Err(7));
+/// ```
+pub fn primality_witness(value: u64) -> Result<(), u64> {
+    if value < 2 {
+        return Err(value);
+    }
+
+    if value & 0x01 == 0 {
+        return if value == 2 { Ok(()) } else { Err(2) };
+    }
+
+    let mut test_fac = 3;
+    while test_fac < factor::MAX_SMALL_NUM {
+        if test_fac * test_fac > value {
+            return Ok(());
+        }
+
+        if value % test_fac == 0 {
+            return Err(test_fac);
+        }
+        test_fac += 2;
+    }
+
+    if is_prime(value) {
+        return Ok(());
+    }
+
+    let mut e = 2;
+    loop {
+        let fac = factor::rho(value, e);
+        if fac != 1 && fac != value {
+            return Err(fac);
+        }
+        e += 1;
+    }
+}
+
 /// Return a `Vec<u64>` of the value's factorization,
 /// using the provided list of primes.
 ///
@@ -497,6 +1207,43 @@ pub fn next_prime(mut n: u64) -> u64 {
     }
 }
 
+/// Return `true` if the Mersenne number `2^p - 1` is prime.
+///
+/// This function uses the Lucas-Lehmer test, which is only
+/// valid for prime exponents, so `false` is returned if `p`
+/// itself is not prime.
+///
+/// # Panics
+///
+/// Panics if `p` is greater than `63`, as `2^p - 1` would not
+/// fit into a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime::lucas_lehmer;
+/// assert_eq!(lucas_lehmer(7), true);
+/// assert_eq!(lucas_lehmer(11), false);
+/// ```
+pub fn lucas_lehmer(p: u64) -> bool {
+    assert!(p <= 63, "2^{} - 1 is larger than u64::MAX!", p);
+    if !is_prime(p) {
+        return false;
+    }
+
+    if p == 2 {
+        return true;
+    }
+
+    let m = ((1u64 << p) - 1) as u128;
+    let mut s: u128 = 4;
+    for _ in 0..(p - 2) {
+        s = (s * s + m - 2) % m;
+    }
+
+    s == 0
+}
+
 /// Simple bit set implementation for prime sieves
 ///
 /// Please note that this struct is not intended for
@@ -571,6 +1318,11 @@ mod tests {
         assert_eq!(eratosthenes(10), atkin(10));
         assert_eq!(eratosthenes(1000), atkin(1000));
 
+        for max in &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 24, 25, 26,
+                     48, 49, 50, 1000, 1021, 65_536, 100_000] {
+            assert_eq!(eratosthenes_odd(*max), eratosthenes(*max));
+        }
+
         assert_eq!(segmented_eratosthenes(0), atkin(0));
         assert_eq!(segmented_eratosthenes(1), atkin(1));
         assert_eq!(segmented_eratosthenes(2), atkin(2));
@@ -582,6 +1334,114 @@ mod tests {
         assert_eq!(segmented_eratosthenes(100000), atkin(100000));
     }
 
+#[test]
+    fn t_prime_sum() {
+        assert_eq!(prime_sum(0), 0);
+        assert_eq!(prime_sum(1), 0);
+        assert_eq!(prime_sum(2), 2);
+        assert_eq!(prime_sum(3), 5);
+        assert_eq!(prime_sum(10), 17);
+        assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+
+        for max in [100, 1_000, 65_535, 65_536, 65_537, 100_000] {
+            let sum: u64 = prime_sieve(max).iter().sum();
+            assert_eq!(prime_sum(max), sum as u128);
+        }
+    }
+
+#[test]
+    fn t_count_primes() {
+        assert_eq!(count_primes(0), 0);
+        assert_eq!(count_primes(1), 0);
+        assert_eq!(count_primes(2), 1);
+        assert_eq!(count_primes(3), 2);
+        assert_eq!(count_primes(10), 4);
+
+        for max in [100, 1_000, 65_535, 65_536, 65_537, 100_000] {
+            assert_eq!(count_primes(max), prime_sieve(max).len() as u64);
+        }
+
+        assert_eq!(count_primes(1_000_000), 78_498);
+        assert_eq!(count_primes(1_000_000), 
+                   ::prime_count::prime_count(1_000_000));
+    }
+
+#[test]
+    fn t_for_each_prime() {
+        let mut primes: Vec<u64> = Vec::new();
+        for_each_prime(1, |p| { primes.push(p); ControlFlow::Continue(()) });
+        assert_eq!(primes, vec![]);
+
+        for_each_prime(1_000_000, |p| { 
+            primes.push(p); 
+            ControlFlow::Continue(()) 
+        });
+        assert_eq!(primes, prime_sieve(1_000_000));
+
+        let mut count = 0;
+        let mut last = 0;
+        for_each_prime(1_000_000, |p| {
+            count += 1;
+            last = p;
+            if count == 100 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(count, 100);
+        assert_eq!(last, nth_prime(99));
+
+        let mut count = 0;
+        for_each_prime(1_000, |_| { count += 1; ControlFlow::Break(()) });
+        assert_eq!(count, 1);
+    }
+
+#[test]
+    fn t_prime_sieve_with_threshold() {
+        for max in [0, 1, 2, 20, 1_000, 65_535, 65_536, 65_537, 300_000] {
+            let expected = prime_sieve(max);
+            assert_eq!(prime_sieve_with_threshold(max, 0), expected);
+            assert_eq!(prime_sieve_with_threshold(max, max), expected);
+            assert_eq!(prime_sieve_with_threshold(max, max + 1), expected);
+            assert_eq!(prime_sieve_with_threshold(max, u64::MAX), expected);
+        }
+    }
+
+#[test]
+    fn t_prime_constellations() {
+        let primes = prime_sieve(10_000);
+        let twins: Vec<Vec<u64>> = primes.windows(2)
+                                         .filter(|w| w[1] - w[0] == 2)
+                                         .map(|w| w.to_vec())
+                                         .collect();
+        assert_eq!(prime_constellations(10_000, &[0, 2]), twins);
+        assert_eq!(prime_constellations(10_000, &[0]).len(), primes.len());
+
+        let quads = prime_constellations(1_000, &[0, 2, 6, 8]);
+        assert_eq!(quads, vec![vec![5, 7, 11, 13], vec![11, 13, 17, 19],
+                               vec![101, 103, 107, 109], 
+                               vec![191, 193, 197, 199],
+                               vec![821, 823, 827, 829]]);
+
+        assert_eq!(prime_constellations(12, &[0, 2, 6, 8]), 
+                   Vec::<Vec<u64>>::new());
+        assert_eq!(prime_constellations(13, &[0, 2, 6, 8]), 
+                   vec![vec![5, 7, 11, 13]]);
+        assert_eq!(prime_constellations(13, &[8, 6, 2, 0]), 
+                   vec![vec![13, 11, 7, 5]]);
+        assert_eq!(prime_constellations(100, &[0, 2, 4]), vec![vec![3, 5, 7]]);
+        assert_eq!(prime_constellations(1, &[0, 2]), Vec::<Vec<u64>>::new());
+        assert_eq!(prime_constellations(100, &[1, 3]), 
+                   prime_constellations(100, &[0, 2]));
+    }
+
+#[test]
+#[should_panic]
+    fn t_prime_constellations_panic() {
+        prime_constellations(100, &[]);
+    }
+
 #[test]
     fn t_is_prime() {
         assert_eq!(is_prime(0), false);
@@ -595,6 +1455,178 @@ mod tests {
         assert_eq!(is_prime(9973), true);
     }
 
+#[test]
+    fn t_is_prime_bpsw() {
+        assert_eq!(is_prime_bpsw(0), false);
+        assert_eq!(is_prime_bpsw(1), false);
+        assert_eq!(is_prime_bpsw(2), true);
+        assert_eq!(is_prime_bpsw(3), true);
+        assert_eq!(is_prime_bpsw(9973), true);
+        assert_eq!(is_prime_bpsw(1_000_000_007), true);
+        assert_eq!(is_prime_bpsw(4_294_967_291), true);
+        assert_eq!(is_prime_bpsw(18_446_744_073_709_551_557), true);
+        assert_eq!(is_prime_bpsw(u64::MAX), false);
+        assert_eq!(is_prime_bpsw(4_294_967_291 * 4_294_967_279), false);
+        assert_eq!(is_prime_bpsw(4_294_967_291 * 4_294_967_291), false);
+
+        // strong pseudoprimes to base two, and Carmichael numbers
+        for n in [2047, 3277, 4033, 4681, 8321, 561, 1105, 1729,
+                  3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(strong_fermat(n, 2) || n % 3 == 0 || n % 5 == 0 ||
+                    n % 7 == 0);
+            assert_eq!(is_prime_bpsw(n), false);
+        }
+
+        // strong Lucas pseudoprimes
+        for n in [5459, 5777, 10877, 16109, 18971] {
+            assert!(strong_lucas(n));
+            assert_eq!(is_prime_bpsw(n), false);
+        }
+
+        for n in 0..200_000 {
+            assert_eq!(is_prime_bpsw(n), is_prime(n));
+        }
+
+        for n in (1_000_000_000_000..1_000_000_010_000).step_by(7) {
+            assert_eq!(is_prime_bpsw(n), is_prime(n));
+        }
+    }
+
+#[test]
+    fn t_is_carmichael() {
+        for n in [561, 1105, 1729, 2465, 2821, 6601, 8911, 41041, 825265,
+                  3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(is_carmichael(n));
+        }
+
+        // Fermat pseudoprimes to base two that are not Carmichael numbers
+        for n in [341, 645, 1387, 2047, 3277, 4033, 4681, 8321] {
+            assert!(modular::pow_mod(2, n - 1, n) == 1);
+            assert!(!is_carmichael(n));
+        }
+
+        for n in [0, 1, 2, 3, 4, 9, 560, 562, 563, 1_000_000_007,
+                  4_294_967_291 * 4_294_967_279, u64::MAX] {
+            assert!(!is_carmichael(n));
+        }
+
+        let carmichael: Vec<u64> = (0..100_000).filter(|n| is_carmichael(*n))
+                                               .collect();
+        assert_eq!(carmichael, vec![561, 1105, 1729, 2465, 2821, 6601, 8911,
+                                    10585, 15841, 29341, 41041, 46657, 52633,
+                                    62745, 63973, 75361]);
+
+        for n in carmichael {
+            assert!(!is_prime(n));
+            for a in 2..100 {
+                if factor::gcd(a, n) == 1 {
+                    assert_eq!(modular::pow_mod(a, n - 1, n), 1);
+                }
+            }
+        }
+    }
+
+#[test]
+    fn t_primality_tester() {
+        let testers = [PrimalityTester::new(0), PrimalityTester::new(2),
+                       PrimalityTester::new(10), PrimalityTester::new(1_000),
+                       PrimalityTester::default()];
+        assert_eq!(testers[3].bound(), 1_000);
+        assert_eq!(testers[4].bound(), S_SIEVE_SIZE);
+
+        for tester in testers.iter() {
+            for n in 0..100_000 {
+                assert_eq!(tester.is_prime(n), is_prime(n));
+            }
+
+            for n in (1_000_000_000_000..1_000_000_002_000).step_by(3) {
+                assert_eq!(tester.is_prime(n), is_prime(n));
+            }
+
+            // strong pseudoprimes to several bases, and Carmichael numbers
+            for n in [2047, 3277, 4033, 4681, 8321, 561, 1105, 1729,
+                      1_373_653, 25_326_001, 3_215_031_751, 
+                      2_152_302_898_747, 3_474_749_660_383,
+                      341_550_071_728_321, 3_825_123_056_546_413_051] {
+                assert_eq!(tester.is_prime(n), false);
+            }
+
+            for n in [1_000_000_007, 4_294_967_291, 
+                      18_446_744_073_709_551_557] {
+                assert_eq!(tester.is_prime(n), true);
+            }
+
+            assert_eq!(tester.is_prime(4_294_967_291 * 4_294_967_279), false);
+            assert_eq!(tester.is_prime(4_294_967_291 * 4_294_967_291), false);
+            assert_eq!(tester.is_prime(u64::MAX), false);
+        }
+
+        let tester = PrimalityTester::default();
+        for n in (u64::MAX - 100_000)..u64::MAX {
+            assert_eq!(tester.is_prime(n), is_prime_bpsw(n));
+        }
+    }
+
+#[test]
+    fn t_gaussian_prime() {
+        assert_eq!(gaussian_norm(0, 0), 0);
+        assert_eq!(gaussian_norm(1, 1), 2);
+        assert_eq!(gaussian_norm(-2, 1), 5);
+        assert_eq!(gaussian_norm(i64::MIN, i64::MIN), 1 << 127);
+
+        assert!(is_gaussian_prime(1, 1));
+        assert!(is_gaussian_prime(1, -1));
+        assert!(is_gaussian_prime(2, 1));
+        assert!(is_gaussian_prime(-2, 1));
+        assert!(is_gaussian_prime(3, 0));
+        assert!(is_gaussian_prime(0, -3));
+        assert!(is_gaussian_prime(0, 7));
+        assert!(is_gaussian_prime(4, 5));
+
+        assert!(!is_gaussian_prime(0, 0));
+        assert!(!is_gaussian_prime(1, 0));
+        assert!(!is_gaussian_prime(0, -1));
+        assert!(!is_gaussian_prime(2, 0));
+        assert!(!is_gaussian_prime(5, 0));
+        assert!(!is_gaussian_prime(0, 13));
+        assert!(!is_gaussian_prime(9, 0));
+        assert!(!is_gaussian_prime(2, 2));
+        assert!(!is_gaussian_prime(3, 4));
+    }
+
+#[test]
+#[should_panic]
+    fn t_gaussian_prime_panic() {
+        is_gaussian_prime(i64::MAX, i64::MAX);
+    }
+
+#[test]
+    fn t_primality_witness() {
+        assert_eq!(primality_witness(0), Err(0));
+        assert_eq!(primality_witness(1), Err(1));
+        assert_eq!(primality_witness(2), Ok(()));
+        assert_eq!(primality_witness(4), Err(2));
+        assert_eq!(primality_witness(97), Ok(()));
+        assert_eq!(primality_witness(9973), Ok(()));
+        assert_eq!(primality_witness(4_294_967_291), Ok(()));
+
+        match primality_witness(91) {
+            Err(d) => assert!(d == 7 || d == 13),
+            Ok(()) => panic!("91 is composite!"),
+        }
+
+        for n in 2..10_000 {
+            assert_eq!(primality_witness(n).is_ok(), is_prime(n));
+        }
+
+        // 99991 * 1000003, no factor below MAX_SMALL_NUM
+        let n = 99_991 * 1_000_003;
+        match primality_witness(n) {
+            Err(d) => assert!(d != 1 && d != n && n % d == 0),
+            Ok(()) => panic!("{} is composite!", n),
+        }
+    }
+
 #[test]
     fn t_factorize() {
         let vec: Vec<u64> = Vec::new();
@@ -637,6 +1669,58 @@ mod tests {
         assert_eq!(nth_prime(1_000_000), 15_485_867);
     }
 
+#[test]
+    fn t_primality_reason() {
+        assert_eq!(primality_reason(0), Primality::Unit);
+        assert_eq!(primality_reason(1), Primality::Unit);
+        assert_eq!(primality_reason(2), Primality::Prime);
+        assert_eq!(primality_reason(3), Primality::Prime);
+        assert_eq!(primality_reason(4), Primality::Even);
+        assert_eq!(primality_reason(7), Primality::Prime);
+        assert_eq!(primality_reason(9), Primality::DivisibleBy(3));
+        assert_eq!(primality_reason(25), Primality::DivisibleBy(5));
+        assert_eq!(primality_reason(91), Primality::DivisibleBy(7));
+        assert_eq!(primality_reason(u64::MAX), Primality::DivisibleBy(3));
+        assert_eq!(primality_reason(u64::MAX - 1), Primality::Even);
+        assert_eq!(primality_reason(999_983 * 1_000_003),
+                   Primality::DivisibleBy(999_983));
+        assert_eq!(primality_reason(1_000_000_007), Primality::Prime);
+
+        for n in 0..10_000 {
+            assert_eq!(primality_reason(n) == Primality::Prime, is_prime(n));
+            if let Primality::DivisibleBy(p) = primality_reason(n) {
+                assert!(is_prime(p));
+                assert_eq!(n % p, 0);
+                assert!(p > 2 && p * p <= n);
+            }
+        }
+    }
+
+#[test]
+    fn t_prime_index() {
+        assert_eq!(prime_index(0), None);
+        assert_eq!(prime_index(1), None);
+        assert_eq!(prime_index(2), Some(0));
+        assert_eq!(prime_index(3), Some(1));
+        assert_eq!(prime_index(4), None);
+        assert_eq!(prime_index(97), Some(24));
+        assert_eq!(prime_index(100), None);
+        assert_eq!(prime_index(101), Some(25));
+        assert_eq!(prime_index(15_485_867), Some(1_000_000));
+        assert_eq!(prime_index(15_485_869), None);
+
+        for (n, p) in prime_sieve(10_000).into_iter().enumerate() {
+            assert_eq!(prime_index(p), Some(n as u64));
+        }
+    }
+
+#[test]
+    fn t_lucas_lehmer() {
+        let exponents: Vec<u64> = (0..64).filter(|p| lucas_lehmer(*p))
+                                         .collect();
+        assert_eq!(exponents, vec![2, 3, 5, 7, 13, 17, 19, 31, 61]);
+    }
+
 #[test]
 #[ignore]
     fn t_nth_prime_long() {
@@ -4,8 +4,12 @@
 //! the prime-counting function for both single and multiple
 //! values.
 
+use super::factor;
 use super::prime::prime_sieve;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Constant string of the uppercase Pi symbol,
 /// often used to represent the prime-counting function.
 pub const PI_SYMBOL: &'static str = "π";
@@ -37,16 +41,117 @@ pub const PI_SYMBOL: &'static str = "π";
 /// assert_eq!(prime_count(10_000), 1_229);
 /// ```
 pub fn prime_count(x: u64) -> u64 {
+    prime_count_with(x, &PrimeCountConfig::default())
+}
+
+/// Configuration for `prime_count_with()`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime_count::PrimeCountConfig;
+/// let config = PrimeCountConfig::new().with_cache_size(64);
+/// assert_eq!(config.cache_size, 64);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrimeCountConfig {
+    /// The largest dimension of the cache used to store values
+    /// of the partial sieve function `phi(m, n)`.
+    ///
+    /// The cache holds up to `cache_size * cache_size` `u64`s,
+    /// but is never larger than needed for the value being counted.
+    /// Larger caches use more memory, but can speed up the
+    /// computation for large values. Defaults to `1024`.
+    pub cache_size: usize,
+}
+
+impl PrimeCountConfig {
+    /// Return a `PrimeCountConfig` with the default settings.
+    pub fn new() -> PrimeCountConfig {
+        PrimeCountConfig { cache_size: CACHE_SIZE }
+    }
+
+    /// Return this `PrimeCountConfig` with a cache size of `cache_size`.
+    pub fn with_cache_size(mut self, cache_size: usize) -> PrimeCountConfig {
+        self.cache_size = cache_size;
+        self
+    }
+}
+
+impl Default for PrimeCountConfig {
+    fn default() -> PrimeCountConfig {
+        PrimeCountConfig::new()
+    }
+}
+
+/// Return the number of prime numbers less than or equal to `x`,
+/// using the settings in `config`.
+///
+/// This function works in the same way as `prime_count()`, see the
+/// documentation of `prime_count()` for more information, and
+/// `PrimeCountConfig` for a description of the settings.
+///
+/// # Panics
+/// 
+/// Panics if `prime_sieve()` panics, see the documentation of
+/// `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime_count::*;
+/// let config = PrimeCountConfig::new().with_cache_size(4096);
+/// assert_eq!(prime_count_with(1_000_000, &config), 78_498);
+/// ```
+pub fn prime_count_with(x: u64, config: &PrimeCountConfig) -> u64 {
     match x {
         0 | 1 => 0,
         2     => 1,
         3 | 4 => 2,
         5     => 3,
-        _     => lehmer(x, &prime_sieve((x as f64).sqrt() as u64 + 1),
-                        &mut vec![vec![0u64; CACHE_SIZE]; CACHE_SIZE]),
+        _     => {
+            let primes = prime_sieve((x as f64).sqrt() as u64 + 1);
+            let mut phi_cache = phi_cache_for(x, &primes, config.cache_size);
+            lehmer(x, &primes, &mut phi_cache)
+        }
     }
 }
 
+/// Return the number of prime numbers less than or equal to `x`,
+/// or `None` if the computation would take more than `max_phi_calls`
+/// calls to the partial sieve function `phi(m, n)`.
+///
+/// This function works in the same way as `prime_count()`, but counts
+/// the calls made to `phi(m, n)`, which make up most of the work done,
+/// and gives up once the count exceeds `max_phi_calls`. This allows
+/// the time spent on very large `x` to be capped.
+///
+/// Values of `x` less than `100` never need to call `phi(m, n)`.
+///
+/// # Panics
+/// 
+/// Panics if `prime_sieve()` panics, see the documentation of
+/// `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime_count::prime_count_timeout;
+/// assert_eq!(prime_count_timeout(1_000_000, 1_000_000), Some(78_498));
+/// assert_eq!(prime_count_timeout(1_000_000, 10), None);
+/// ```
+pub fn prime_count_timeout(x: u64, max_phi_calls: u64) -> Option<u64> {
+    if x < 6 {
+        return Some(prime_count(x));
+    }
+
+    let primes = prime_sieve((x as f64).sqrt() as u64 + 1);
+    let mut phi_cache = phi_cache_for(x, &primes, CACHE_SIZE);
+    phi_cache.max_phi_calls = max_phi_calls;
+    lehmer_checked(x, &primes, &mut phi_cache)
+}
+
 /// Calculate the value of the prime-counting function for each
 /// value in `data`, and return a new `Vec<u64>` of the results.
 ///
@@ -56,6 +161,9 @@ pub fn prime_count(x: u64) -> u64 {
 /// with the modification that caches are preserved between calculations.
 /// This allows for much faster computation of multiple values.
 ///
+/// The caches are sized according to the largest value in `data`,
+/// so small queries do not pay for a large allocation.
+///
 /// # Panics
 /// 
 /// Panics if `prime_sieve()` panics, see the documentation of
@@ -74,7 +182,6 @@ pub fn prime_count_all(data: &[u64]) -> Vec<u64> {
     }
 
     let mut counts: Vec<u64> = Vec::new();
-    let mut phi_cache = vec![vec![0u64; CACHE_SIZE]; CACHE_SIZE];
 
     let mut largest_index = 0;
     let mut largest_val = 0;
@@ -86,6 +193,7 @@ pub fn prime_count_all(data: &[u64]) -> Vec<u64> {
     }
 
     let primes = prime_sieve((data[largest_index] as f64).sqrt() as u64 + 1);
+    let mut phi_cache = phi_cache_for(largest_val, &primes, CACHE_SIZE);
     let max_val = lehmer(largest_val, &primes, &mut phi_cache);
 
     for i in 0..data.len() {
@@ -104,8 +212,154 @@ pub fn prime_count_all(data: &[u64]) -> Vec<u64> {
     counts
 }
 
+/// Return the number of prime numbers less than or equal to `x`,
+/// using the Lucy_Hedgehog sieve.
+///
+/// Rather than recursing like `prime_count()`, this function sieves
+/// over the values `x / i`, which are the only values needed to compute
+/// the prime-counting function of `x`. For each prime `p` up to `sqrt(x)`,
+/// the counts are updated with
+///
+/// ```text
+/// S(v) -= S(v / p) - S(p - 1)
+/// ```
+///
+/// for every `v >= p^2`, taking `O(x^(3/4))` time and `O(sqrt(x))` memory.
+/// This is iterative, so it does not risk a stack overflow for large `x`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime_count::prime_count_lucy;
+/// assert_eq!(prime_count_lucy(1_000), 168);
+/// assert_eq!(prime_count_lucy(10_000), 1_229);
+/// ```
+pub fn prime_count_lucy(x: u64) -> u64 {
+    if x < 2 {
+        return 0;
+    }
+
+    let r = factor::isqrt(x);
+
+    // small[v] holds S(v) for v <= r, large[i] holds S(x / i)
+    let mut small: Vec<u64> = (0..(r + 1)).map(|v| v.saturating_sub(1))
+                                          .collect();
+    let mut large: Vec<u64> = vec![0; r as usize + 1];
+    for i in 1..(r + 1) {
+        large[i as usize] = x / i - 1;
+    }
+
+    for p in 2..(r + 1) {
+        if small[p as usize] == small[p as usize - 1] {
+            continue;
+        }
+
+        let sp = small[p as usize - 1];
+        let p2 = p * p;
+
+        let end = r.min(x / p2);
+        for i in 1..(end + 1) {
+            let d = i * p;
+            let sub = if d <= r { large[d as usize] } 
+                      else { small[(x / d) as usize] };
+            large[i as usize] -= sub - sp;
+        }
+
+        for v in (p2..(r + 1)).rev() {
+            small[v as usize] -= small[(v / p) as usize] - sp;
+        }
+    }
+
+    large[1]
+}
+
+/// Return the start of the interval of length `window` in [0, max]
+/// that contains the most primes, along with the number of primes 
+/// it contains, formatted as `(start, count)`.
+///
+/// The interval starting at `start` is `[start, start + window - 1]`.
+/// If several intervals contain the same number of primes, the one that
+/// starts first is returned. If `window` is larger than `max + 1`, the 
+/// whole range is used, so `(0, prime_count(max))` is returned.
+///
+/// This function uses a single call to `prime_sieve(max)`, then slides
+/// the window across the range one step at a time.
+///
+/// # Panics
+///
+/// Panics if `window` is zero, or if `prime_sieve()` panics, see the 
+/// documentation of `prime_sieve()` for more information.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::prime_count::densest_interval;
+/// assert_eq!(densest_interval(100, 10), (2, 5));
+/// ```
+pub fn densest_interval(max: u64, window: u64) -> (u64, u64) {
+    assert!(window != 0, "window must be positive!");
+
+    let primes = prime_sieve(max);
+    if window > max {
+        return (0, primes.len() as u64);
+    }
+
+    let mut is_p = vec![false; max as usize + 1];
+    for p in &primes {
+        is_p[*p as usize] = true;
+    }
+
+    let window = window as usize;
+    let mut count = is_p[..window].iter().filter(|p| **p).count();
+    let mut best = (0, count);
+    for start in 1..(is_p.len() - window + 1) {
+        if is_p[start - 1] {
+            count -= 1;
+        }
+        if is_p[start + window - 1] {
+            count += 1;
+        }
+
+        if count > best.1 {
+            best = (start, count);
+        }
+    }
+
+    (best.0 as u64, best.1 as u64)
+}
+
+// default largest dimension of the phi cache
 const CACHE_SIZE: usize = 1024;
-type CacheT = Vec<Vec<u64>>;
+
+// cache of phi values, along with a count of the calls made to phi(),
+// and the number of calls allowed before giving up
+struct CacheT {
+    table: Vec<Vec<u64>>,
+    phi_calls: u64,
+    max_phi_calls: u64,
+}
+
+impl CacheT {
+    fn new(rows: usize, cols: usize) -> CacheT {
+        CacheT { 
+            table: vec![vec![0u64; cols]; rows], 
+            phi_calls: 0,
+            max_phi_calls: u64::MAX,
+        }
+    }
+}
+
+// helper function to create a phi cache just large enough for `x`
+//
+// `phi(m, n)` is only ever called with `m <= x` and `n <= pi(x^(1/4))`,
+// so there is no need to allocate the full `size` square for small values.
+fn phi_cache_for(x: u64, primes: &[u64], size: usize) -> CacheT {
+    let rows = (x as usize).saturating_add(1).min(size);
+    let a = num_below((x as f64).powf(0.25).round() as u64, primes);
+    let cols = (a as usize + 1).min(size);
+
+    CacheT::new(rows, cols)
+}
 
 const SMALL_PI: [u64; 100] = 
 [0 , 0 , 1 , 2 , 2 , 3 , 3 , 4 , 4 , 4 ,
@@ -120,71 +374,86 @@ const SMALL_PI: [u64; 100] =
  24, 24, 24, 24, 24, 24, 24, 25, 25, 25];
 
 fn lehmer(x: u64, primes: &Vec<u64>, phi_cache: &mut CacheT) -> u64 {
+    lehmer_checked(x, primes, phi_cache).expect("phi() call budget exceeded!")
+}
+
+// helper function to compute Lehmer's formula, returning `None` if
+// more than `phi_cache.max_phi_calls` calls to phi() are made
+fn lehmer_checked(x: u64, primes: &Vec<u64>, 
+                  phi_cache: &mut CacheT) -> Option<u64> {
     if x < 100 {
-        return SMALL_PI[x as usize];
+        return Some(SMALL_PI[x as usize]);
     }
     
     if x < primes[primes.len() - 1] {
-        return num_below(x, primes);
+        return Some(num_below(x, primes));
     }
 
 
-    let a = lehmer((x as f64).powf(0.25).round() as u64, 
-                     primes, phi_cache) + 1;
-    let b = lehmer((x as f64).sqrt().round() as u64, 
-                         primes, phi_cache) + 1;
-    let c = lehmer((x as f64).cbrt().round() as u64, 
-                     primes, phi_cache);
+    let a = lehmer_checked((x as f64).powf(0.25).round() as u64, 
+                           primes, phi_cache)? + 1;
+    let b = lehmer_checked((x as f64).sqrt().round() as u64, 
+                           primes, phi_cache)? + 1;
+    let c = lehmer_checked((x as f64).cbrt().round() as u64, 
+                           primes, phi_cache)?;
 
-    let mut pi = phi(x, a - 1, primes, phi_cache) + 
+    let mut pi = phi(x, a - 1, primes, phi_cache)? + 
                  ((b + a - 4) * (b - a + 1)) / 2;
 
     for i in a..b {
         let x_tmp = x / primes[i as usize - 1];
-        pi -= lehmer(x_tmp, primes, phi_cache);
+        pi -= lehmer_checked(x_tmp, primes, phi_cache)?;
 
         if i > c {
             continue;
         }
 
-        let bi = lehmer((x_tmp as f64).sqrt() as u64, primes, phi_cache) + 1;
+        let bi = lehmer_checked((x_tmp as f64).sqrt() as u64, 
+                                primes, phi_cache)? + 1;
         for j in i..bi {
             pi += j - 1;
-            pi -= lehmer(x_tmp / primes[j as usize - 1], primes, phi_cache);
+            pi -= lehmer_checked(x_tmp / primes[j as usize - 1], 
+                                 primes, phi_cache)?;
         }
     }
 
-    pi
+    Some(pi)
 }
 
-fn phi(m: u64, n: u64, primes: &[u64], cache: &mut CacheT) -> u64 {
+fn phi(m: u64, n: u64, primes: &[u64], cache: &mut CacheT) -> Option<u64> {
+    cache.phi_calls += 1;
+    if cache.phi_calls > cache.max_phi_calls {
+        return None;
+    }
+
     if n == 0 || m == 0 {
-        return m;
+        return Some(m);
     }
 
     if n == 1 {
-        return (m + 1) / 2;
+        return Some((m + 1) / 2);
     }
 
     if m <= primes[n as usize - 1] {
-        return 1;
+        return Some(1);
     }
 
-    if m < CACHE_SIZE as u64 && n < CACHE_SIZE as u64 {
-        if cache[m as usize][n as usize] == 0 {
-            let val = phi(m, n - 1, primes, cache) - 
-                      phi(m / primes[n as usize - 1], n - 1, primes, cache);
-            cache[m as usize][n as usize] = val;
+    let (row, col) = (m as usize, n as usize);
+    if row < cache.table.len() && col < cache.table[row].len() {
+        if cache.table[row][col] == 0 {
+            let val = phi(m, n - 1, primes, cache)? - 
+                      phi(m / primes[n as usize - 1], n - 1, primes, cache)?;
+            cache.table[row][col] = val;
         }
 
-        return cache[m as usize][n as usize];
+        return Some(cache.table[row][col]);
     }
 
-    phi(m, n - 1, primes, cache) - 
-    phi(m / primes[n as usize - 1], n - 1, primes, cache)
+    Some(phi(m, n - 1, primes, cache)? - 
+         phi(m / primes[n as usize - 1], n - 1, primes, cache)?)
 }
 
-fn num_below(x: u64, vec: &Vec<u64>) -> u64 {
+fn num_below(x: u64, vec: &[u64]) -> u64 {
     let res = vec.binary_search(&x);
     match res {
         Ok(n)  => n as u64 + 1,
@@ -217,4 +486,157 @@ mod tests {
         assert_eq!(prime_count_all(&vec![1, 2, 3, 4, 5, 6]).len(), 6);
     }
 
+#[test]
+    fn t_prime_count_with() {
+        let primes = prime_sieve(10_001);
+
+        for x in [0, 5, 100, 1_000, 1_000_000, 99_999_999] {
+            let mut calls: Vec<u64> = Vec::new();
+            for size in [0, 16, CACHE_SIZE, 4 * CACHE_SIZE] {
+                let config = PrimeCountConfig::new().with_cache_size(size);
+                assert_eq!(prime_count_with(x, &config), prime_count(x));
+
+                if x >= 100 {
+                    let mut cache = phi_cache_for(x, &primes, size);
+                    lehmer(x, &primes, &mut cache);
+                    calls.push(cache.phi_calls);
+                }
+            }
+
+            // larger caches should never need more calls to phi()
+            assert!(calls.windows(2).all(|w| w[0] >= w[1]));
+        }
+
+        let mut small = phi_cache_for(99_999_999, &primes, 16);
+        let mut large = phi_cache_for(99_999_999, &primes, CACHE_SIZE);
+        lehmer(99_999_999, &primes, &mut small);
+        lehmer(99_999_999, &primes, &mut large);
+        assert!(small.phi_calls > large.phi_calls);
+
+        assert_eq!(PrimeCountConfig::default(), PrimeCountConfig::new());
+        assert_eq!(PrimeCountConfig::new().cache_size, CACHE_SIZE);
+    }
+
+#[test]
+    fn t_prime_count_timeout() {
+        assert_eq!(prime_count_timeout(0, 0), Some(0));
+        assert_eq!(prime_count_timeout(5, 0), Some(3));
+        assert_eq!(prime_count_timeout(99, 0), Some(25));
+        assert_eq!(prime_count_timeout(1_000, 0), None);
+        assert_eq!(prime_count_timeout(1_000, u64::MAX), Some(168));
+        assert_eq!(prime_count_timeout(99_999_999, 100), None);
+        assert_eq!(prime_count_timeout(99_999_999, u64::MAX), Some(5_761_455));
+        assert_eq!(prime_count_timeout(1_000_000_000_000, 1_000), None);
+
+        // the budget is exact
+        for x in [1_000, 12_345, 1_000_000, 99_999_999] {
+            let primes = prime_sieve((x as f64).sqrt() as u64 + 1);
+            let mut cache = phi_cache_for(x, &primes, CACHE_SIZE);
+            lehmer(x, &primes, &mut cache);
+
+            assert_eq!(prime_count_timeout(x, cache.phi_calls), 
+                       Some(prime_count(x)));
+            assert_eq!(prime_count_timeout(x, cache.phi_calls - 1), None);
+        }
+    }
+
+#[test]
+#[cfg(feature = "serde")]
+    fn t_prime_count_config_serde() {
+        use serde_test::{assert_tokens, Token};
+
+        let config = PrimeCountConfig::new().with_cache_size(64);
+        assert_tokens(&config, &[
+            Token::Struct { name: "PrimeCountConfig", len: 1 },
+            Token::Str("cache_size"),
+            Token::U64(64),
+            Token::StructEnd,
+        ]);
+    }
+
+#[test]
+    fn t_prime_count_lucy() {
+        for x in 0..1_000 {
+            assert_eq!(prime_count_lucy(x), prime_count(x));
+        }
+
+        for x in [1_024, 65_535, 65_536, 999_999, 1_000_000, 
+                  1_234_567, 9_999_991, 10_000_000] {
+            assert_eq!(prime_count_lucy(x), prime_count(x));
+        }
+    }
+
+#[test]
+#[ignore]
+    fn t_prime_count_lucy_long() {
+        assert_eq!(prime_count_lucy(10_000_000_000), 455_052_511);
+    }
+
+#[test]
+    fn t_densest_interval() {
+        assert_eq!(densest_interval(0, 1), (0, 0));
+        assert_eq!(densest_interval(1, 1), (0, 0));
+        assert_eq!(densest_interval(2, 1), (2, 1));
+        assert_eq!(densest_interval(10, 2), (2, 2));
+        assert_eq!(densest_interval(10, 3), (1, 2));
+        assert_eq!(densest_interval(10, 100), (0, 4));
+        assert_eq!(densest_interval(100, 10), (2, 5));
+        assert_eq!(densest_interval(100, 101), (0, 25));
+        assert_eq!(densest_interval(1_000, 1_000), (0, 168));
+
+        let primes = prime_sieve(100_000);
+        for window in [2, 7, 20, 100, 1_000] {
+            let (start, count) = densest_interval(100_000, window);
+            let recount = primes.iter()
+                                .filter(|p| **p >= start && **p < start + window)
+                                .count() as u64;
+            assert_eq!(count, recount);
+
+            for s in 0..(100_000 - window + 1) {
+                let first = primes.binary_search(&s).unwrap_or_else(|i| i);
+                let last = primes.binary_search(&(s + window))
+                                 .unwrap_or_else(|i| i);
+                assert!((last - first) as u64 <= count);
+                if s < start {
+                    assert!(((last - first) as u64) < count);
+                }
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_densest_interval_panic() {
+        densest_interval(100, 0);
+    }
+
+#[test]
+    fn t_phi_cache_size() {
+        let primes = prime_sieve(10_001);
+
+        let cache = phi_cache_for(1_000, &primes, CACHE_SIZE);
+        assert_eq!(cache.table.len(), 1_000 + 1);
+        assert_eq!(cache.table[0].len(), 4);
+
+        let cache = phi_cache_for(100_000_000, &primes, CACHE_SIZE);
+        assert_eq!(cache.table.len(), CACHE_SIZE);
+        assert_eq!(cache.table[0].len(), 26);
+
+        let cache = phi_cache_for(100_000_000, &primes, 10);
+        assert_eq!(cache.table.len(), 10);
+        assert_eq!(cache.table[0].len(), 10);
+
+        for x in [100, 1_000, 12_345, 1_000_000, 99_999_999] {
+            let mut cache = phi_cache_for(x, &primes, CACHE_SIZE);
+            let expected = lehmer(x, &primes, &mut cache);
+            for size in [0, 1, 16, 100, CACHE_SIZE] {
+                let mut cache = CacheT::new(size, size);
+                assert_eq!(lehmer(x, &primes, &mut cache), expected);
+            }
+        }
+
+        let mut cache = CacheT::new(0, 0);
+        assert_eq!(lehmer(99_999_999, &primes, &mut cache), 5_761_455);
+    }
+
 }
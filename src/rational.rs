@@ -0,0 +1,628 @@
+//! Module for working with rational numbers.
+//!
+//! This module has a `Rational` type for exact arithmetic with
+//! fractions, which are always kept in lowest terms, and functions
+//! for navigating the Stern-Brocot tree and working with mediants
+//! and Farey neighbors.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::factor::gcd;
+
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A rational number, stored as a numerator and denominator.
+///
+/// A `Rational` created with `Rational::new()` is always in lowest
+/// terms, with a positive denominator, so two `Rational`s are equal
+/// only if their numerators and denominators are equal.
+///
+/// Arithmetic is done with `i128` intermediate values, and the result
+/// is reduced before being stored, so operations only overflow if the
+/// reduced result does not fit in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::rational::Rational;
+/// let a = Rational::new(1, 2);
+/// let b = Rational::new(1, 3);
+/// assert_eq!(a + b, Rational::new(5, 6));
+/// assert_eq!(a * b, Rational::new(1, 6));
+/// assert_eq!((a - b).to_string(), "1/6");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RationalParts"))]
+pub struct Rational {
+    // the numerator of the rational number
+    num: i64,
+    // the denominator of the rational number, always positive
+    den: i64,
+}
+
+// unchecked numerator and denominator of a deserialized Rational,
+// which are reduced with Rational::try_from() before being used
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Rational")]
+struct RationalParts {
+    num: i64,
+    den: i64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RationalParts> for Rational {
+    type Error = String;
+
+    fn try_from(parts: RationalParts) -> Result<Rational, String> {
+        Rational::checked_from_i128(parts.num as i128, parts.den as i128)
+            .ok_or_else(|| format!("invalid rational number {}/{}",
+                                   parts.num, parts.den))
+    }
+}
+
+impl Rational {
+    /// Return the rational number `num / den` in lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero, or if the reduced fraction does not
+    /// fit in an `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::rational::Rational;
+    /// let r = Rational::new(2, -4);
+    /// assert_eq!((r.num(), r.den()), (-1, 2));
+    /// ```
+    pub fn new(num: i64, den: i64) -> Rational {
+        Rational::from_i128(num as i128, den as i128)
+    }
+
+    /// Return the numerator of this rational number in lowest terms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::rational::Rational;
+    /// assert_eq!(Rational::new(6, -4).num(), -3);
+    /// ```
+    pub fn num(&self) -> i64 {
+        self.num
+    }
+
+    /// Return the denominator of this rational number in lowest
+    /// terms, which is always positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::rational::Rational;
+    /// assert_eq!(Rational::new(6, -4).den(), 2);
+    /// ```
+    pub fn den(&self) -> i64 {
+        self.den
+    }
+
+    /// Return the value of this rational number as an `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::rational::Rational;
+    /// assert_eq!(Rational::new(3, 4).to_f64(), 0.75);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Return the reciprocal of this rational number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this rational number is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reikna::rational::Rational;
+    /// assert_eq!(Rational::new(-3, 4).recip(), Rational::new(-4, 3));
+    /// ```
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.den, self.num)
+    }
+
+    // helper function to reduce a fraction of i128s into a Rational
+    fn from_i128(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "denominator cannot be zero!");
+
+        match Rational::checked_from_i128(num, den) {
+            Some(r) => r,
+            None => panic!("rational number {}/{} is too large for an i64!",
+                           num, den),
+        }
+    }
+
+    // helper function to reduce a fraction of i128s into a Rational,
+    // returning None if the denominator is zero or the result is too large
+    fn checked_from_i128(num: i128, den: i128) -> Option<Rational> {
+        if den == 0 {
+            return None;
+        }
+
+        let (num, den) = reduce(num, den);
+        if num < i64::MIN as i128 || num > i64::MAX as i128 ||
+           den > i64::MAX as i128 {
+            return None;
+        }
+
+        Some(Rational { num: num as i64, den: den as i64 })
+    }
+}
+
+// helper function to reduce a fraction, keeping the denominator positive
+pub(crate) fn reduce(num: i128, den: i128) -> (i128, i128) {
+    let mut a = num.unsigned_abs();
+    let mut b = den.unsigned_abs();
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+
+    let g = if den < 0 { -(a as i128) } else { a as i128 };
+    (num / g, den / g)
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+}
+
+impl fmt::Display for Rational {
+    /// Format the rational number as `num/den`, or as `num` if
+    /// the denominator is one.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.num as i128 * other.den as i128)
+            .cmp(&(other.num as i128 * self.den as i128))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        let g = gcd(self.den as u64, other.den as u64) as i128;
+        let den = self.den as i128 / g * other.den as i128;
+        let num = self.num as i128 * (den / self.den as i128) +
+                  other.num as i128 * (den / other.den as i128);
+        Rational::from_i128(num, den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::from_i128(self.num as i128 * other.num as i128,
+                            self.den as i128 * other.den as i128)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        assert!(other.num != 0, "cannot divide by zero!");
+        Rational::from_i128(self.num as i128 * other.den as i128,
+                            self.den as i128 * other.num as i128)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational::from_i128(-(self.num as i128), self.den as i128)
+    }
+}
+
+/// Return the path from the root of the Stern-Brocot tree to the
+/// fraction `num / den`, as a `Vec` of `'L'` and `'R'` moves.
+///
+/// The fraction is reduced first, and the root `1/1` has an empty
+/// path. The lengths of the runs of equal moves are the terms of
+/// the continued fraction of `num / den`, with the last term reduced
+/// by one, so the path is found with the Euclidean algorithm.
+///
+/// # Panics
+///
+/// Panics if `num` or `den` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::rational::stern_brocot_path;
+/// assert_eq!(stern_brocot_path(3, 5), vec!['L', 'R', 'L']);
+/// assert_eq!(stern_brocot_path(1, 1), vec![]);
+/// ```
+pub fn stern_brocot_path(num: u64, den: u64) -> Vec<char> {
+    assert!(num != 0 && den != 0, 
+            "{}/{} is not in the Stern-Brocot tree!", num, den);
+
+    let g = gcd(num, den);
+    let (mut num, mut den) = (num / g, den / g);
+
+    let mut path: Vec<char> = Vec::new();
+    while num != den {
+        if num > den {
+            let run = (num - 1) / den;
+            path.extend((0..run).map(|_| 'R'));
+            num -= run * den;
+        } else {
+            let run = (den - 1) / num;
+            path.extend((0..run).map(|_| 'L'));
+            den -= run * num;
+        }
+    }
+
+    path
+}
+
+/// Return the fraction found by following `path` from the root
+/// of the Stern-Brocot tree.
+///
+/// This is the inverse of `stern_brocot_path()`. Each move replaces
+/// one of the bounds of the current interval with the mediant of the
+/// bounds, starting from the interval `(0/1, 1/0)`.
+///
+/// # Panics
+///
+/// Panics if `path` contains anything other than `'L'` and `'R'`, or if 
+/// the resulting fraction does not fit in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::rational::{from_path, Rational};
+/// assert_eq!(from_path(&['L', 'R', 'L']), Rational::new(3, 5));
+/// assert_eq!(from_path(&[]), Rational::from(1));
+/// ```
+pub fn from_path(path: &[char]) -> Rational {
+    let (mut left_num, mut left_den): (i64, i64) = (0, 1);
+    let (mut right_num, mut right_den): (i64, i64) = (1, 0);
+
+    for step in path {
+        let num = left_num.checked_add(right_num);
+        let den = left_den.checked_add(right_den);
+        let (num, den) = match (num, den) {
+            (Some(num), Some(den)) => (num, den),
+            _ => panic!("fraction is too large for an i64!"),
+        };
+
+        match *step {
+            'L' => { right_num = num; right_den = den; },
+            'R' => { left_num = num; left_den = den; },
+            _ => panic!("invalid Stern-Brocot move '{}'!", step),
+        }
+    }
+
+    let num = left_num.checked_add(right_num);
+    let den = left_den.checked_add(right_den);
+    match (num, den) {
+        (Some(num), Some(den)) => Rational::new(num, den),
+        _ => panic!("fraction is too large for an i64!"),
+    }
+}
+
+/// Return the mediant of `a` and `b`, that is, the fraction
+/// `(a.num() + b.num()) / (a.den() + b.den())`, in lowest terms.
+///
+/// The mediant of two different fractions always lies strictly 
+/// between them. Each node of the Stern-Brocot tree is the mediant
+/// of the bounds of its interval, see `from_path()`.
+///
+/// # Panics
+///
+/// Panics if the mediant does not fit in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::rational::{mediant, Rational};
+/// assert_eq!(mediant(Rational::new(0, 1), Rational::new(1, 1)), 
+///            Rational::new(1, 2));
+/// assert_eq!(mediant(Rational::new(1, 3), Rational::new(1, 2)), 
+///            Rational::new(2, 5));
+/// ```
+pub fn mediant(a: Rational, b: Rational) -> Rational {
+    Rational::from_i128(a.num as i128 + b.num as i128,
+                        a.den as i128 + b.den as i128)
+}
+
+/// Return `true` if `a` and `b` are Farey neighbors, that is, if
+/// `|a.num() * b.den() - b.num() * a.den()| = 1`.
+///
+/// Neighboring terms of every Farey sequence are Farey neighbors,
+/// and the first fraction to appear between two Farey neighbors,
+/// in the Farey sequences or in the Stern-Brocot tree, is their 
+/// mediant.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::rational::{are_farey_neighbors, Rational};
+/// assert!(are_farey_neighbors(Rational::new(1, 3), Rational::new(1, 2)));
+/// assert!(!are_farey_neighbors(Rational::new(1, 3), Rational::new(2, 3)));
+/// ```
+pub fn are_farey_neighbors(a: Rational, b: Rational) -> bool {
+    (a.num as i128 * b.den as i128 - b.num as i128 * a.den as i128).abs() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+#[test]
+    fn t_new() {
+        let r = Rational::new(2, 4);
+        assert_eq!((r.num(), r.den()), (1, 2));
+        let r = Rational::new(-2, 4);
+        assert_eq!((r.num(), r.den()), (-1, 2));
+        let r = Rational::new(2, -4);
+        assert_eq!((r.num(), r.den()), (-1, 2));
+        let r = Rational::new(-2, -4);
+        assert_eq!((r.num(), r.den()), (1, 2));
+        let r = Rational::new(0, -7);
+        assert_eq!((r.num(), r.den()), (0, 1));
+        assert_eq!(Rational::new(12, 3), Rational::from(4));
+        assert_eq!(Rational::new(i64::MIN, i64::MIN), Rational::from(1));
+        assert_eq!(Rational::new(i64::MAX, -1), Rational::from(-i64::MAX));
+        assert_eq!(Rational::new(3, 4).to_f64(), 0.75);
+    }
+
+#[test]
+#[should_panic]
+    fn t_new_panic() {
+        Rational::new(1, 0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_new_panic_2() {
+        Rational::new(i64::MIN, -1);
+    }
+
+#[test]
+    fn t_arithmetic() {
+        let a = Rational::new(1, 2);
+        let b = Rational::new(-2, 3);
+
+        assert_eq!(a + b, Rational::new(-1, 6));
+        assert_eq!(a - b, Rational::new(7, 6));
+        assert_eq!(a * b, Rational::new(-1, 3));
+        assert_eq!(a / b, Rational::new(-3, 4));
+        assert_eq!(-a, Rational::new(-1, 2));
+        assert_eq!(b.recip(), Rational::new(-3, 2));
+        assert_eq!(a + a, Rational::from(1));
+        assert_eq!(a - a, Rational::from(0));
+
+        let big = Rational::new(i64::MAX, 2);
+        assert_eq!(big * Rational::new(2, i64::MAX), Rational::from(1));
+        assert_eq!(big - big, Rational::from(0));
+        assert_eq!(Rational::new(1, i64::MAX) + Rational::new(1, i64::MAX),
+                   Rational::new(2, i64::MAX));
+
+        let mut sum = Rational::from(0);
+        for n in 1..20 {
+            sum = sum + Rational::new(1, n * (n + 1));
+        }
+        assert_eq!(sum, Rational::new(19, 20));
+    }
+
+#[test]
+#[should_panic]
+    fn t_div_panic() {
+        let _ = Rational::new(1, 2) / Rational::from(0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_overflow_panic() {
+        let _ = Rational::from(i64::MAX) + Rational::from(1);
+    }
+
+#[test]
+    fn t_comparison() {
+        let a = Rational::new(1, 3);
+        let b = Rational::new(1, 2);
+        assert!(a < b);
+        assert!(-b < -a);
+        assert!(Rational::new(2, 6) == a);
+        assert!(Rational::new(i64::MAX - 1, i64::MAX) < Rational::from(1));
+        assert!(Rational::new(i64::MAX, i64::MAX - 1) > Rational::from(1));
+
+        let mut values = vec![b, Rational::from(-1), a, Rational::new(2, 5)];
+        values.sort();
+        assert_eq!(values, vec![Rational::from(-1), a, Rational::new(2, 5), b]);
+    }
+
+#[test]
+    fn t_stern_brocot() {
+        use super::super::continued_fraction::from_rational;
+
+        assert_eq!(stern_brocot_path(1, 1), vec![]);
+        assert_eq!(stern_brocot_path(1, 2), vec!['L']);
+        assert_eq!(stern_brocot_path(2, 1), vec!['R']);
+        assert_eq!(stern_brocot_path(3, 5), vec!['L', 'R', 'L']);
+        assert_eq!(stern_brocot_path(6, 10), vec!['L', 'R', 'L']);
+        assert_eq!(stern_brocot_path(4, 1), vec!['R'; 3]);
+        assert_eq!(stern_brocot_path(1, 1_000), vec!['L'; 999]);
+
+        assert_eq!(from_path(&['L', 'R', 'L']), Rational::new(3, 5));
+        assert_eq!(from_path(&['R'; 3]), Rational::from(4));
+        assert_eq!(from_path(&['L'; 1_000]), Rational::new(1, 1_001));
+
+        for num in 1..40 {
+            for den in 1..40 {
+                let path = stern_brocot_path(num, den);
+                assert_eq!(from_path(&path), 
+                           Rational::new(num as i64, den as i64));
+
+                // the run lengths of the path are the continued fraction
+                // terms, with the last term reduced by one
+                let mut runs: Vec<u64> = Vec::new();
+                let mut last = 'R';
+                let mut run = 0;
+                for step in &path {
+                    if *step == last {
+                        run += 1;
+                    } else {
+                        runs.push(run);
+                        last = *step;
+                        run = 1;
+                    }
+                }
+                runs.push(run + 1);
+
+                assert_eq!(runs, from_rational(num, den));
+            }
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_stern_brocot_panic() {
+        stern_brocot_path(0, 1);
+    }
+
+#[test]
+#[should_panic]
+    fn t_from_path_panic() {
+        from_path(&['L', 'X']);
+    }
+
+#[test]
+    fn t_mediant() {
+        use super::super::factor::farey_sequence;
+
+        let zero = Rational::from(0);
+        let one = Rational::from(1);
+        assert_eq!(mediant(zero, one), Rational::new(1, 2));
+        assert_eq!(mediant(one, zero), Rational::new(1, 2));
+        assert_eq!(mediant(Rational::new(1, 3), Rational::new(1, 2)), 
+                   Rational::new(2, 5));
+        assert_eq!(mediant(Rational::new(-1, 2), Rational::new(1, 2)), zero);
+        assert_eq!(mediant(Rational::new(1, 3), Rational::new(1, 3)), 
+                   Rational::new(1, 3));
+        assert_eq!(mediant(Rational::new(1, 3), Rational::new(2, 6)), 
+                   Rational::new(1, 3));
+
+        assert!(are_farey_neighbors(zero, one));
+        assert!(are_farey_neighbors(one, zero));
+        assert!(are_farey_neighbors(Rational::new(1, 3), Rational::new(1, 2)));
+        assert!(are_farey_neighbors(Rational::new(-1, 1), zero));
+        assert!(!are_farey_neighbors(Rational::new(1, 3), Rational::new(2, 3)));
+        assert!(!are_farey_neighbors(one, one));
+        assert!(are_farey_neighbors(Rational::new(i64::MAX - 1, i64::MAX),
+                                    one));
+
+        // neighbors in a Farey sequence are Farey neighbors, and the 
+        // first fraction inserted between them is their mediant
+        let to_rational = |(n, d): (u64, u64)| Rational::new(n as i64, 
+                                                              d as i64);
+        for n in 1..30 {
+            let seq: Vec<Rational> = farey_sequence(n).into_iter()
+                                                      .map(to_rational)
+                                                      .collect();
+            let next: Vec<Rational> = farey_sequence(n + 1).into_iter()
+                                                           .map(to_rational)
+                                                           .collect();
+            for pair in seq.windows(2) {
+                assert!(are_farey_neighbors(pair[0], pair[1]));
+
+                let m = mediant(pair[0], pair[1]);
+                assert!(pair[0] < m && m < pair[1]);
+                if m.den() as u64 == n + 1 {
+                    assert!(next.contains(&m));
+                } else {
+                    assert!(!seq.contains(&m));
+                }
+            }
+        }
+
+        let path = ['L', 'R', 'R', 'L'];
+        let parent = from_path(&path[..3]);
+        let child = from_path(&path);
+        assert!(are_farey_neighbors(parent, child));
+    }
+
+#[test]
+#[should_panic]
+    fn t_mediant_panic() {
+        mediant(Rational::from(i64::MAX), Rational::new(1, 2));
+    }
+
+#[test]
+    fn t_display() {
+        assert_eq!(Rational::new(1, 2).to_string(), "1/2");
+        assert_eq!(Rational::new(2, -4).to_string(), "-1/2");
+        assert_eq!(Rational::new(6, 3).to_string(), "2");
+        assert_eq!(Rational::from(0).to_string(), "0");
+    }
+
+#[test]
+#[cfg(feature = "serde")]
+    fn t_rational_serde() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error,
+                         assert_tokens, Token};
+
+        let tokens = |num, den| [
+            Token::Struct { name: "Rational", len: 2 },
+            Token::Str("num"), Token::I64(num),
+            Token::Str("den"), Token::I64(den),
+            Token::StructEnd,
+        ];
+
+        assert_tokens(&Rational::new(-1, 2), &tokens(-1, 2));
+        assert_de_tokens(&Rational::new(1, 2), &tokens(2, 4));
+        assert_de_tokens(&Rational::new(-1, 2), &tokens(1, -2));
+        assert_de_tokens_error::<Rational>(&tokens(1, 0),
+                                           "invalid rational number 1/0");
+        assert_de_tokens_error::<Rational>(&tokens(i64::MIN, -1),
+            "invalid rational number -9223372036854775808/-1");
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! This module has functions for computing the value of
 //! the totient function, both for single and multiple
-//! values.
+//! values, sieving and caching the totients of every value
+//! up to a bound, the related Dedekind psi function, and
+//! for finding primitive roots and multiplicative orders.
 
 use super::prime;
 use super::factor;
+use super::modular;
 
 /// Constant string of the uppercase Phi symbol,
 /// often used to represent the totient function.
@@ -13,12 +16,12 @@ pub const PHI_SYMBOL: &'static str = "Φ";
 
 // helper function to do the totient calculation
 fn totient_calc(n: u64, factors: Vec<u64>) -> u64 {
-    let mut totient = n as f64;
+    let mut totient = n;
     for factor in factors {
-        totient *= 1.0 - (1.0 / factor as f64);
+        totient = totient / factor * (factor - 1);
     }
 
-    totient as u64
+    totient
 }
 
 /// Calculate the value of Euler's totient function for `n`.
@@ -90,6 +93,403 @@ pub fn totient_all(data: Vec<u64>) -> Vec<u64> {
     totients
 }
 
+/// Return a `Vec<u64>` of the totient of every value in [0, max],
+/// that is, `Φ(0), Φ(1), ..., Φ(max)`.
+///
+/// Each value starts as `n`, and for every prime `p`, the value of
+/// each multiple of `p` is multiplied by `1 - 1/p`. Unlike `totient()`,
+/// no factoring is done, and the values are computed with exact
+/// integer arithmetic. `Φ(0)` is given as `0`.
+///
+/// # Panics
+///
+/// Panics if `max` cannot be cast into a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::totient_sieve;
+/// assert_eq!(totient_sieve(10), vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+/// ```
+pub fn totient_sieve(max: u64) -> Vec<u64> {
+    assert!(max < usize::MAX as u64,
+            "sieve max {} is larger than machine word size!", max);
+    let max = max as usize;
+
+    let mut totients: Vec<u64> = (0..(max as u64 + 1)).collect();
+    for p in 2..(max + 1) {
+        if totients[p] != p as u64 {
+            continue;
+        }
+
+        let mut k = p;
+        while k <= max {
+            totients[k] -= totients[k] / p as u64;
+            k += p;
+        }
+    }
+
+    totients
+}
+
+/// A cache of the values of the totient function up to a bound.
+///
+/// The totients of every value up to the bound are computed once, with
+/// `totient_sieve()`, when the cache is created, and are then looked
+/// up on each call. Values above the bound fall back to `totient()`.
+///
+/// This is well suited to computing the totients of many small values.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::TotientCache;
+/// let cache = TotientCache::new(1_000);
+/// assert_eq!(cache.totient(36), 12);
+/// assert_eq!(cache.totient(1_000_003), 1_000_002);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TotientCache {
+    bound: u64,
+    totients: Vec<u64>,
+}
+
+impl TotientCache {
+    /// Return a new `TotientCache`, sieving the totients in [0, `bound`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `totient_sieve()` panics, see the documentation for
+    /// this function for more information.
+    pub fn new(bound: u64) -> TotientCache {
+        TotientCache { bound, totients: totient_sieve(bound) }
+    }
+
+    /// Return the bound the totients of this cache were sieved to.
+    pub fn bound(&self) -> u64 {
+        self.bound
+    }
+
+    /// Return the value of Euler's totient function for `n`.
+    ///
+    /// The value is looked up if `n` is in [1, `bound`], and is
+    /// computed with `totient()` otherwise.
+    pub fn totient(&self, n: u64) -> u64 {
+        if n == 0 || n > self.bound {
+            return totient(n);
+        }
+
+        self.totients[n as usize]
+    }
+}
+
+/// Return `true` if `n` is a perfect totient number, that is, if
+/// the sum of the iterated totients of `n`
+///
+/// ```text
+/// Φ(n) + Φ(Φ(n)) + ... + 1
+/// ```
+///
+/// is equal to `n`.
+///
+/// As `Φ(n) < n` for every `n > 1`, the iteration always reaches one,
+/// taking at most `2 * log2(n)` steps. Zero and one are not considered
+/// perfect totient numbers.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::is_perfect_totient;
+/// assert_eq!(is_perfect_totient(9), true); // 6 + 2 + 1 = 9
+/// assert_eq!(is_perfect_totient(4), false);
+/// ```
+pub fn is_perfect_totient(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    let mut sum: u128 = 0;
+    let mut val = n;
+    while val != 1 {
+        val = totient(val);
+        sum += val as u128;
+    }
+
+    sum == n as u128
+}
+
+/// Calculate the value of the Dedekind psi function for `n`.
+///
+/// The Dedekind psi function is given by the product formula
+///
+/// ```text
+/// ψ(n) = n * (1 + 1/p1) * (1 + 1/p2) * ... * (1 + 1/pk)
+/// ```
+///
+/// over the distinct primes `p1, p2, ..., pk` dividing `n`. Unlike
+/// `totient()`, this is computed with integer arithmetic, dividing `n`
+/// by each prime before multiplying by `p + 1`, so the result is exact.
+///
+/// # Panics
+///
+/// Panics if `n` is zero, or if `ψ(n)` is larger than `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::dedekind_psi;
+/// assert_eq!(dedekind_psi(6), 12);
+/// assert_eq!(dedekind_psi(9), 12);
+/// ```
+pub fn dedekind_psi(n: u64) -> u64 {
+    assert!(n != 0, "Dedekind psi is only defined for positive integers!");
+
+    let mut factors = factor::quick_factorize(n);
+    factors.dedup();
+
+    let mut psi = n;
+    for p in factors {
+        psi = (psi / p).checked_mul(p + 1)
+                       .expect("Dedekind psi is larger than u64::MAX!");
+    }
+
+    psi
+}
+
+/// Return the smallest primitive root modulo `n`, or `None` if
+/// no primitive root exists.
+///
+/// A primitive root modulo `n` is a value `g` whose powers produce
+/// every value coprime to `n`, that is, `g` has multiplicative order
+/// `Φ(n)`. Primitive roots only exist if `n` is `1`, `2`, `4`, `p^k`,
+/// or `2p^k`, where `p` is an odd prime.
+///
+/// Each candidate `g` is tested by checking that
+///
+/// ```text
+/// g^(Φ(n) / q) != 1 (mod n)
+/// ```
+///
+/// for every prime factor `q` of `Φ(n)`, using `modular::pow_mod()`.
+///
+/// Every value is congruent to `0` modulo `1`, so `Some(0)` is
+/// returned for `n = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::primitive_root;
+/// assert_eq!(primitive_root(7), Some(3));
+/// assert_eq!(primitive_root(11), Some(2));
+/// assert_eq!(primitive_root(8), None);
+/// ```
+pub fn primitive_root(n: u64) -> Option<u64> {
+    match n {
+        0 => return None,
+        1 => return Some(0),
+        2 => return Some(1),
+        4 => return Some(3),
+        _ => (),
+    }
+
+    if !has_primitive_root(n) {
+        return None;
+    }
+
+    let phi = totient(n);
+    let mut phi_primes = factor::quick_factorize(phi);
+    phi_primes.dedup();
+
+    (2..n).find(|g| factor::coprime(*g, n) &&
+                    phi_primes.iter()
+                              .all(|q| modular::pow_mod(*g, phi / q, n) != 1))
+}
+
+/// Return the number of primitive roots modulo `n`.
+///
+/// If any primitive root modulo `n` exists, there are exactly
+/// `Φ(Φ(n))` of them in [0, n), otherwise there are none. See
+/// `primitive_root()` for which values of `n` have primitive roots.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::count_primitive_roots;
+/// assert_eq!(count_primitive_roots(7), 2);
+/// assert_eq!(count_primitive_roots(8), 0);
+/// ```
+pub fn count_primitive_roots(n: u64) -> u64 {
+    if !has_primitive_root(n) {
+        return 0;
+    }
+
+    totient(totient(n))
+}
+
+// helper function to check if `n` is 1, 2, 4, p^k, or 2p^k
+fn has_primitive_root(n: u64) -> bool {
+    match n {
+        0 => return false,
+        1 | 2 | 4 => return true,
+        _ => (),
+    }
+
+    let odd = if n & 0x01 == 0 { n / 2 } else { n };
+    if odd & 0x01 == 0 {
+        return false;
+    }
+
+    let mut odd_primes = factor::quick_factorize(odd);
+    odd_primes.dedup();
+    odd_primes.len() == 1
+}
+
+/// Return the multiplicative order of `a` modulo `n`, that is, the
+/// smallest `k > 0` such that
+///
+/// ```text
+/// a^k = 1 (mod n)
+/// ```
+///
+/// or `None` if `a` is not coprime to `n`, or if `n` is zero.
+///
+/// The order always divides `Φ(n)`, so it is found by factoring
+/// `Φ(n)` with `factor::factorize_exp()` and removing every prime
+/// factor that does not change `a^k = 1`. `Φ(n)` is computed exactly
+/// from the factorization of `n`, so large values of `n` are handled
+/// correctly, but factoring them can take a long time.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::multiplicative_order;
+/// assert_eq!(multiplicative_order(10, 7), Some(6));
+/// assert_eq!(multiplicative_order(2, 7), Some(3));
+/// assert_eq!(multiplicative_order(2, 6), None);
+/// ```
+pub fn multiplicative_order(a: u64, n: u64) -> Option<u64> {
+    if n == 0 || !factor::coprime(a, n) {
+        return None;
+    }
+
+    if n == 1 {
+        return Some(1);
+    }
+
+    let factors = factor::factorize_exp(n);
+    let phi: u64 = factors.iter()
+                          .map(|&(p, e)| (p - 1) * p.pow(e - 1))
+                          .product();
+    let mut order = phi;
+    for (q, _) in factor::factorize_exp(phi) {
+        while order % q == 0 && modular::pow_mod(a, order / q, n) == 1 {
+            order /= q;
+        }
+    }
+
+    Some(order)
+}
+
+/// Return a sorted `Vec<u64>` of every `n` such that `Φ(n) = m`.
+///
+/// Rather than testing every candidate, this function searches over
+/// the primes `p` for which `p - 1` divides `m`, since only these
+/// primes can appear in the factorization of a solution. Each prime
+/// power `p^k` is tried in turn, dividing `m` by `Φ(p^k)` and
+/// recursing with the remaining smaller primes.
+///
+/// If there are no solutions, such as for odd `m > 1`, an empty `Vec`
+/// is returned. Solutions larger than `u64::MAX` are not included.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::inverse_totient;
+/// assert_eq!(inverse_totient(4), vec![5, 8, 10, 12]);
+/// assert_eq!(inverse_totient(14), vec![]);
+/// ```
+pub fn inverse_totient(m: u64) -> Vec<u64> {
+    let mut solutions: Vec<u64> = Vec::new();
+    if m == 0 {
+        return solutions;
+    }
+
+    let primes = inverse_totient_primes(m);
+    inverse_totient_search(m, &primes, primes.len(), 1, &mut solutions, 
+                           false);
+
+    solutions.sort();
+    solutions
+}
+
+/// Return `true` if `m` is a nontotient, that is, if there is no
+/// `n` such that `Φ(n) = m`.
+///
+/// This uses the same search as `inverse_totient()`, but stops as
+/// soon as a single solution is found. Every odd number greater than
+/// one is a nontotient, as is zero.
+///
+/// # Examples
+///
+/// ```
+/// use reikna::totient::is_nontotient;
+/// assert_eq!(is_nontotient(14), true);
+/// assert_eq!(is_nontotient(4), false);
+/// ```
+pub fn is_nontotient(m: u64) -> bool {
+    if m == 0 || (m > 1 && m & 0x01 == 1) {
+        return true;
+    }
+
+    let mut solutions: Vec<u64> = Vec::new();
+    let primes = inverse_totient_primes(m);
+    inverse_totient_search(m, &primes, primes.len(), 1, &mut solutions, 
+                           true);
+
+    solutions.is_empty()
+}
+
+// helper function to find the primes `p` where `p - 1` divides `m`
+fn inverse_totient_primes(m: u64) -> Vec<u64> {
+    factor::divisors(m).iter()
+                       .map(|d| d + 1)
+                       .filter(|p| prime::is_prime(*p))
+                       .collect()
+}
+
+// helper function to find the solutions of Φ(n) = m using the
+// first `count` primes of `primes`, `n` is the product so far,
+// if `first` is set the search stops after the first solution
+fn inverse_totient_search(m: u64, primes: &[u64], count: usize, n: u64,
+                          solutions: &mut Vec<u64>, first: bool) {
+    if m == 1 {
+        solutions.push(n);
+    }
+
+    for i in (0..count).rev() {
+        if first && !solutions.is_empty() {
+            return;
+        }
+
+        let p = primes[i];
+        if m % (p - 1) != 0 {
+            continue;
+        }
+
+        let mut rem = m / (p - 1);
+        let mut next = n.checked_mul(p);
+        while let Some(val) = next {
+            inverse_totient_search(rem, primes, i, val, solutions, first);
+
+            if rem % p != 0 {
+                break;
+            }
+            rem /= p;
+            next = val.checked_mul(p);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +508,7 @@ mod tests {
         assert_eq!(totient(9), 6);
         assert_eq!(totient(99), 60);
         assert_eq!(totient(10_809_483_705_896), 5_404_726_850_224);
+        assert_eq!(totient(36_028_797_018_964_050), 8_229_403_665_208_320);
     }
 
 #[test]
@@ -119,4 +520,253 @@ mod tests {
         assert_eq!(totient_all(vec![10, 20, 30, 40]), vec![4, 8, 8, 16]);
     }
 
+#[test]
+    fn t_totient_sieve() {
+        assert_eq!(totient_sieve(0), vec![0]);
+        assert_eq!(totient_sieve(1), vec![0, 1]);
+        assert_eq!(totient_sieve(10), vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+
+        let totients = totient_sieve(10_000);
+        assert_eq!(totients.len(), 10_001);
+        assert_eq!(totients[1..], totient_all((1..10_001).collect())[..]);
+    }
+
+#[test]
+    fn t_totient_cache() {
+        let cache = TotientCache::new(10_000);
+        assert_eq!(cache.bound(), 10_000);
+        let totients = totient_all((0..10_001).collect());
+        for (n, phi) in totients.iter().enumerate() {
+            assert_eq!(cache.totient(n as u64), *phi);
+        }
+
+        assert_eq!(cache.totient(10_007), 10_006);
+        assert_eq!(cache.totient(1_000_003), 1_000_002);
+        assert_eq!(cache.totient(10_809_483_705_896), 5_404_726_850_224);
+
+        let cache = TotientCache::new(0);
+        assert_eq!(cache.totient(0), 1);
+        assert_eq!(cache.totient(1), 1);
+        assert_eq!(cache.totient(36), 12);
+    }
+
+#[test]
+    fn t_is_perfect_totient() {
+        assert_eq!(is_perfect_totient(0), false);
+        assert_eq!(is_perfect_totient(1), false);
+        assert_eq!(is_perfect_totient(2), false);
+        assert_eq!(is_perfect_totient(3), true);
+        assert_eq!(is_perfect_totient(4), false);
+        assert_eq!(is_perfect_totient(9), true);
+        assert_eq!(is_perfect_totient(15), true);
+        assert_eq!(is_perfect_totient(27), true);
+        assert_eq!(is_perfect_totient(3_u64.pow(12)), true);
+        assert_eq!(is_perfect_totient(u64::MAX), false);
+
+        let perfect: Vec<u64> = (0..300).filter(|n| is_perfect_totient(*n))
+                                        .collect();
+        assert_eq!(perfect, vec![3, 9, 15, 27, 39, 81, 111, 183, 243, 255]);
+    }
+
+#[test]
+    fn t_dedekind_psi() {
+        assert_eq!(dedekind_psi(1), 1);
+        assert_eq!(dedekind_psi(2), 3);
+        assert_eq!(dedekind_psi(6), 12);
+        assert_eq!(dedekind_psi(12), 24);
+        assert_eq!(dedekind_psi(1 << 63), 3 << 62);
+
+        for p in prime::prime_sieve(200) {
+            assert_eq!(dedekind_psi(p), p + 1);
+            assert_eq!(dedekind_psi(p * p), p * p + p);
+        }
+        assert_eq!(dedekind_psi(1_000_000_007), 1_000_000_008);
+        assert_eq!(dedekind_psi(65_537 * 65_537 * 65_537), 
+                   65_537 * 65_537 * 65_538);
+
+        // ψ(n) is the sum of n / d over the squarefree divisors d of n
+        let mobius = factor::mobius_sieve(1_000);
+        for n in 1..1_000 {
+            let sum: u64 = factor::divisors(n)
+                           .into_iter()
+                           .filter(|d| mobius[*d as usize] != 0)
+                           .map(|d| n / d)
+                           .sum();
+            assert_eq!(dedekind_psi(n), sum);
+        }
+    }
+
+#[test]
+#[should_panic]
+    fn t_dedekind_psi_panic() {
+        dedekind_psi(0);
+    }
+
+#[test]
+#[should_panic]
+    fn t_dedekind_psi_panic_2() {
+        dedekind_psi(u64::MAX - 1);
+    }
+
+#[test]
+    fn t_primitive_root() {
+        assert_eq!(primitive_root(0), None);
+        assert_eq!(primitive_root(1), Some(0));
+        assert_eq!(primitive_root(2), Some(1));
+        assert_eq!(primitive_root(3), Some(2));
+        assert_eq!(primitive_root(4), Some(3));
+        assert_eq!(primitive_root(7), Some(3));
+        assert_eq!(primitive_root(8), None);
+        assert_eq!(primitive_root(11), Some(2));
+        assert_eq!(primitive_root(12), None);
+        assert_eq!(primitive_root(15), None);
+        assert_eq!(primitive_root(18), Some(5));
+        assert_eq!(primitive_root(23), Some(5));
+        assert_eq!(primitive_root(25), Some(2));
+        assert_eq!(primitive_root(41), Some(6));
+        assert_eq!(primitive_root(1_000_000_007), Some(5));
+
+        // check against the definition for small n
+        for n in 2..200 {
+            let phi = totient(n);
+            let order = |g: u64| (1..(phi + 1)).find(|k| {
+                modular::pow_mod(g, *k, n) == 1
+            }).unwrap_or(0);
+
+            let brute = (1..n).find(|g| factor::coprime(*g, n) && 
+                                        order(*g) == phi);
+            assert_eq!(primitive_root(n), brute);
+        }
+    }
+
+#[test]
+    fn t_count_primitive_roots() {
+        assert_eq!(count_primitive_roots(0), 0);
+        assert_eq!(count_primitive_roots(1), 1);
+        assert_eq!(count_primitive_roots(2), 1);
+        assert_eq!(count_primitive_roots(4), 1);
+        assert_eq!(count_primitive_roots(7), 2);
+        assert_eq!(count_primitive_roots(7), totient(6));
+        assert_eq!(count_primitive_roots(8), 0);
+        assert_eq!(count_primitive_roots(12), 0);
+        assert_eq!(count_primitive_roots(1_000_000_007), 
+                   totient(1_000_000_006));
+        assert_eq!(count_primitive_roots(36_028_797_018_964_051),
+                   8_229_403_665_208_320);
+
+        // check against the definition for small n
+        for n in 2..200 {
+            let phi = totient(n);
+            let order = |g: u64| (1..(phi + 1)).find(|k| {
+                modular::pow_mod(g, *k, n) == 1
+            }).unwrap_or(0);
+
+            let brute = (1..n).filter(|g| factor::coprime(*g, n) && 
+                                          order(*g) == phi)
+                              .count() as u64;
+            assert_eq!(count_primitive_roots(n), brute);
+            assert_eq!(count_primitive_roots(n) == 0, 
+                       primitive_root(n).is_none());
+        }
+    }
+
+#[test]
+    fn t_multiplicative_order() {
+        assert_eq!(multiplicative_order(0, 0), None);
+        assert_eq!(multiplicative_order(3, 0), None);
+        assert_eq!(multiplicative_order(5, 1), Some(1));
+        assert_eq!(multiplicative_order(1, 2), Some(1));
+        assert_eq!(multiplicative_order(10, 3), Some(1));
+        assert_eq!(multiplicative_order(10, 7), Some(6));
+        assert_eq!(multiplicative_order(2, 7), Some(3));
+        assert_eq!(multiplicative_order(17, 7), Some(6));
+        assert_eq!(multiplicative_order(2, 6), None);
+        assert_eq!(multiplicative_order(3, 8), Some(2));
+        assert_eq!(multiplicative_order(10, 1_000_000_007),
+                   Some(1_000_000_006));
+        assert_eq!(multiplicative_order(2, u64::MAX), Some(64));
+
+        // check against the definition for small n
+        for n in 1..60 {
+            for a in 0..(n + 2) {
+                assert_eq!(multiplicative_order(a, n), brute_order(a, n));
+            }
+        }
+    }
+
+#[test]
+#[ignore]
+    fn t_multiplicative_order_long() {
+        for n in 1..200 {
+            for a in 0..(2 * n) {
+                assert_eq!(multiplicative_order(a, n), brute_order(a, n));
+            }
+        }
+    }
+
+    // helper function to find the multiplicative order by brute force
+    fn brute_order(a: u64, n: u64) -> Option<u64> {
+        if !factor::coprime(a, n) {
+            return None;
+        }
+
+        (1..(n + 1)).find(|k| modular::pow_mod(a, *k, n) == 1 % n)
+    }
+
+#[test]
+    fn t_inverse_totient() {
+        assert_eq!(inverse_totient(0), vec![]);
+        assert_eq!(inverse_totient(1), vec![1, 2]);
+        assert_eq!(inverse_totient(2), vec![3, 4, 6]);
+        assert_eq!(inverse_totient(3), vec![]);
+        assert_eq!(inverse_totient(4), vec![5, 8, 10, 12]);
+        assert_eq!(inverse_totient(14), vec![]);
+        assert_eq!(inverse_totient(24), vec![35, 39, 45, 52, 56, 70, 72, 
+                                             78, 84, 90]);
+
+        let solutions = inverse_totient(1 << 32);
+        assert_eq!(solutions.len(), 32);
+        assert_eq!(solutions[0], 1 << 33);
+        assert_eq!(solutions[31], 4 * (u32::MAX as u64));
+        assert!(solutions.iter().all(|n| totient(*n) == 1 << 32));
+
+        // every solution is at most 2m^2, so the search can be checked
+        // against the totients of every value up to that bound
+        let max = 60;
+        let totients = totient_all((1..(2 * max * max + 1)).collect());
+        for m in 1..(max + 1) {
+            let brute: Vec<u64> = (1..(2 * max * max + 1))
+                                  .filter(|n| totients[*n as usize - 1] == m)
+                                  .collect();
+            assert_eq!(inverse_totient(m), brute);
+        }
+
+        for n in [1_000_003, 123_456_789, 4_294_967_296] {
+            assert!(inverse_totient(totient(n)).contains(&n));
+        }
+    }
+
+#[test]
+    fn t_is_nontotient() {
+        assert_eq!(is_nontotient(0), true);
+        assert_eq!(is_nontotient(1), false);
+        assert_eq!(is_nontotient(2), false);
+        assert_eq!(is_nontotient(4), false);
+        assert_eq!(is_nontotient(14), true);
+        assert_eq!(is_nontotient(26), true);
+        assert_eq!(is_nontotient(1 << 32), false);
+
+        for m in 0..500 {
+            if m > 1 && m & 0x01 == 1 {
+                assert!(is_nontotient(m));
+            }
+            assert_eq!(is_nontotient(m), inverse_totient(m).is_empty());
+        }
+
+        let even_nontotients: Vec<u64> = (1..100).filter(|m| is_nontotient(*m) &&
+                                                             m & 0x01 == 0)
+                                                 .collect();
+        assert_eq!(even_nontotients, vec![14, 26, 34, 38, 50, 62, 68, 74, 
+                                          76, 86, 90, 94, 98]);
+    }
 }